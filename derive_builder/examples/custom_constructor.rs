@@ -34,6 +34,7 @@ pub struct ApiClient {
 }
 
 impl ApiClient {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(host: impl Into<String>, key: impl Into<String>) -> ApiClientBuilder {
         ApiClientBuilder {
             host: Some(host.into()),
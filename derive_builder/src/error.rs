@@ -4,6 +4,13 @@ use std::{error::Error, fmt};
 #[cfg(not(feature = "std"))]
 use core::fmt;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
 /// Runtime error when a `build()` method is called and one or more required fields
 /// do not have a value.
 #[derive(Debug, Clone)]
@@ -35,3 +42,504 @@ impl From<&'static str> for UninitializedFieldError {
         Self::new(field_name)
     }
 }
+
+/// Runtime error when a `build()` method is called and one or more required fields
+/// do not have a value, listing all of them.
+///
+/// This is returned instead of [`UninitializedFieldError`] by builders with
+/// `#[builder(build_fn(collect_uninitialized))]`.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UninitializedFieldsError(Vec<&'static str>);
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl UninitializedFieldsError {
+    /// Create a new `UninitializedFieldsError` without any fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the name of a field that wasn't initialized.
+    pub fn push(&mut self, field_name: &'static str) {
+        self.0.push(field_name)
+    }
+
+    /// Check if no uninitialized fields have been added.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the names of all fields that weren't initialized, in declaration order.
+    pub fn field_names(&self) -> &[&'static str] {
+        &self.0
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for UninitializedFieldsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fields not initialized: ")?;
+        for (i, field_name) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", field_name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for UninitializedFieldsError {}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<UninitializedFieldError> for UninitializedFieldsError {
+    fn from(e: UninitializedFieldError) -> Self {
+        let mut fields = Self::new();
+        fields.push(e.field_name());
+        fields
+    }
+}
+
+/// Runtime error when the final value of a field with `#[builder(validate(...))]` is invalid,
+/// or an item of a collection with validators in `#[builder(setter(each(...)))]`.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldValidationError {
+    field_name: &'static str,
+    index: Option<usize>,
+    message: String,
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl FieldValidationError {
+    /// Create a new `FieldValidationError` for the specified field name.
+    pub fn new<M: Into<String>>(field_name: &'static str, message: M) -> Self {
+        Self {
+            field_name,
+            index: None,
+            message: message.into(),
+        }
+    }
+
+    /// Create a new `FieldValidationError` for the item at `index` of the collection in the
+    /// specified field.
+    pub fn for_item<M: Into<String>>(field_name: &'static str, index: usize, message: M) -> Self {
+        Self {
+            field_name,
+            index: Some(index),
+            message: message.into(),
+        }
+    }
+
+    /// Get the name of the invalid field.
+    pub fn field_name(&self) -> &'static str {
+        self.field_name
+    }
+
+    /// Get the index of the invalid item, if the error is about an item of a collection.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Get the path of the invalid field or item, e.g. `servers` or `servers[3]`.
+    pub fn field_path(&self) -> String {
+        match self.index {
+            Some(index) => format!("{}[{}]", self.field_name, index),
+            None => self.field_name.to_string(),
+        }
+    }
+
+    /// Get the description of why the field is invalid, e.g. `must be at most 64`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for FieldValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.field_path(), self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FieldValidationError {}
+
+/// Runtime error listing every failed validation of a builder, each with the path of the
+/// invalid field.
+///
+/// This is returned instead of [`FieldValidationError`] and the error of the struct-level
+/// validator by builders with `#[builder(build_fn(collect_validation_errors))]`.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors(Vec<(String, String)>);
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl ValidationErrors {
+    /// Create a new `ValidationErrors` without any errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an error of the field at `field_path`, e.g. `port` or `server.port`.
+    pub fn push<P: Into<String>, M: Into<String>>(&mut self, field_path: P, message: M) {
+        self.0.push((field_path.into(), message.into()))
+    }
+
+    /// Add an error of the item at `index` of the collection in the field `field_name`.
+    pub fn push_item<M: Into<String>>(&mut self, field_name: &str, index: usize, message: M) {
+        self.push(format!("{}[{}]", field_name, index), message)
+    }
+
+    /// Add an error which does not belong to a single field.
+    pub fn push_message<M: Into<String>>(&mut self, message: M) {
+        self.push(String::new(), message)
+    }
+
+    /// Check if no errors have been added.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the number of errors.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Iterate over the field path and message of each error, in the order they were added.
+    ///
+    /// The field path is empty for errors which do not belong to a single field.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(field_path, message)| (field_path.as_str(), message.as_str()))
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (field_path, message)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            if field_path.is_empty() {
+                write!(f, "{}", message)?;
+            } else {
+                write!(f, "`{}`: {}", field_path, message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ValidationErrors {}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<FieldValidationError> for ValidationErrors {
+    fn from(e: FieldValidationError) -> Self {
+        let mut errors = Self::new();
+        errors.push(e.field_path(), e.message);
+        errors
+    }
+}
+
+/// Constraint of a group of fields declared with `#[builder(group(...))]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRule {
+    /// Exactly one of the fields must be set.
+    ExactlyOne,
+    /// At least one of the fields must be set.
+    AtLeastOne,
+    /// If the first field is set, all other fields must be set too.
+    Requires,
+}
+
+impl GroupRule {
+    /// Check the fields named `fields` of the group `group`, where `set` tells whether each
+    /// of them is set.
+    #[cfg(any(feature = "alloc", feature = "std"))]
+    pub fn check(
+        self,
+        group: &'static str,
+        fields: &'static [&'static str],
+        set: &[bool],
+    ) -> Result<(), GroupViolationError> {
+        let count = set.iter().filter(|is_set| **is_set).count();
+        let valid = match self {
+            GroupRule::ExactlyOne => count == 1,
+            GroupRule::AtLeastOne => count >= 1,
+            GroupRule::Requires => !set.first().copied().unwrap_or(false) || count == set.len(),
+        };
+        if valid {
+            return Ok(());
+        }
+
+        Err(GroupViolationError {
+            group,
+            rule: self,
+            fields,
+            set: fields
+                .iter()
+                .zip(set)
+                .filter(|(_, is_set)| **is_set)
+                .map(|(field, _)| *field)
+                .collect(),
+        })
+    }
+}
+
+/// Runtime error when the fields of a group declared with `#[builder(group(...))]` are set
+/// in a combination which violates the rule of the group.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupViolationError {
+    group: &'static str,
+    rule: GroupRule,
+    fields: &'static [&'static str],
+    set: Vec<&'static str>,
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl GroupViolationError {
+    /// Get the name of the violated group.
+    pub fn group(&self) -> &'static str {
+        self.group
+    }
+
+    /// Get the rule of the violated group.
+    pub fn rule(&self) -> GroupRule {
+        self.rule
+    }
+
+    /// Get the names of all fields of the group, in the order they were declared in the group.
+    pub fn fields(&self) -> &'static [&'static str] {
+        self.fields
+    }
+
+    /// Get the names of the fields of the group which were set.
+    pub fn set(&self) -> &[&'static str] {
+        &self.set
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for GroupViolationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write_list<'a>(
+            f: &mut fmt::Formatter,
+            fields: impl Iterator<Item = &'a &'static str>,
+        ) -> fmt::Result {
+            for (i, field) in fields.enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "`{}`", field)?;
+            }
+            Ok(())
+        }
+
+        write!(f, "group `{}`: ", self.group)?;
+        match self.rule {
+            GroupRule::ExactlyOne => {
+                write!(f, "exactly one of ")?;
+                write_list(f, self.fields.iter())?;
+                if self.set.is_empty() {
+                    write!(f, " must be set, but none is set")
+                } else {
+                    write!(f, " must be set, but ")?;
+                    write_list(f, self.set.iter())?;
+                    write!(f, " are set")
+                }
+            }
+            GroupRule::AtLeastOne => {
+                write!(f, "at least one of ")?;
+                write_list(f, self.fields.iter())?;
+                write!(f, " must be set")
+            }
+            GroupRule::Requires => {
+                write!(f, "`{}` requires ", self.fields[0])?;
+                write_list(
+                    f,
+                    self.fields[1..]
+                        .iter()
+                        .filter(|field| !self.set.contains(field)),
+                )?;
+                write!(f, " to be set")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for GroupViolationError {}
+
+/// Runtime error when a fallible setter of a builder with `#[builder(try_setter(defer))]`
+/// failed to convert its argument.
+///
+/// The setter keeps the builder unchanged and records the error, which is returned by the
+/// `build()` method.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrySetterError {
+    field_name: &'static str,
+    message: String,
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl TrySetterError {
+    /// Create a new `TrySetterError` for the specified field name.
+    pub fn new<M: Into<String>>(field_name: &'static str, message: M) -> Self {
+        Self {
+            field_name,
+            message: message.into(),
+        }
+    }
+
+    /// Get the name of the field whose setter failed.
+    pub fn field_name(&self) -> &'static str {
+        self.field_name
+    }
+
+    /// Get the description of the conversion error, as formatted by its `Display` impl.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for TrySetterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.field_name, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for TrySetterError {}
+
+/// Runtime error when the `build()` method of a field with `#[builder(sub_builder)]` fails.
+///
+/// The error keeps the path of the field in which it occurred, including the fields of any
+/// enclosing builders, e.g. `server.tls.cert_path`.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubfieldBuildError {
+    field_path: Vec<String>,
+    /// Description of the failure, or `None` if the field at `field_path` was not initialized.
+    message: Option<String>,
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl SubfieldBuildError {
+    /// Wrap the error of the builder for the field `field_name`.
+    pub fn new<E: Into<SubfieldBuildError>>(field_name: &'static str, error: E) -> Self {
+        let mut error = error.into();
+        error.field_path.insert(0, field_name.to_string());
+        error
+    }
+
+    /// Get the path of the field in which the error occurred, e.g. `server.tls.cert_path`.
+    pub fn field_path(&self) -> String {
+        self.field_path.join(".")
+    }
+
+    /// Check if the error was caused by a field which wasn't initialized.
+    pub fn is_uninitialized(&self) -> bool {
+        self.message.is_none()
+    }
+
+    /// Get the description of the error, unless it was caused by a field which wasn't
+    /// initialized.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for SubfieldBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.message {
+            None => write!(f, "`{}` must be initialized", self.field_path()),
+            Some(message) if self.field_path.is_empty() => write!(f, "{}", message),
+            Some(message) => write!(f, "`{}`: {}", self.field_path(), message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SubfieldBuildError {}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<UninitializedFieldError> for SubfieldBuildError {
+    fn from(e: UninitializedFieldError) -> Self {
+        Self {
+            field_path: Vec::from([e.field_name().to_string()]),
+            message: None,
+        }
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<UninitializedFieldsError> for SubfieldBuildError {
+    fn from(e: UninitializedFieldsError) -> Self {
+        match e.field_names() {
+            [field_name] => UninitializedFieldError::new(field_name).into(),
+            _ => Self {
+                field_path: Vec::new(),
+                message: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<String> for SubfieldBuildError {
+    fn from(message: String) -> Self {
+        Self {
+            field_path: Vec::new(),
+            message: Some(message),
+        }
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<FieldValidationError> for SubfieldBuildError {
+    fn from(e: FieldValidationError) -> Self {
+        Self {
+            field_path: Vec::from([e.field_path()]),
+            message: Some(e.message),
+        }
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<ValidationErrors> for SubfieldBuildError {
+    fn from(e: ValidationErrors) -> Self {
+        Self {
+            field_path: Vec::new(),
+            message: Some(e.to_string()),
+        }
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<TrySetterError> for SubfieldBuildError {
+    fn from(e: TrySetterError) -> Self {
+        Self {
+            field_path: Vec::from([e.field_name.to_string()]),
+            message: Some(e.message),
+        }
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl From<GroupViolationError> for SubfieldBuildError {
+    fn from(e: GroupViolationError) -> Self {
+        Self {
+            field_path: Vec::new(),
+            message: Some(e.to_string()),
+        }
+    }
+}
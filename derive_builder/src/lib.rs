@@ -294,7 +294,94 @@
 //! ```
 //! If you want to set the value to None when unset, then enable `default` on this field (or do not use `strip_option`).
 //!
-//! Limitation: only the `Option` type name is supported, not type alias nor `std::option::Option`.
+//! Only `Option`, `std::option::Option` and `core::option::Option` are recognized, so a field
+//! whose type is an alias, such as `type MaybeString = Option<String>`, needs the wrapped type
+//! given with `strip_option(inner = "String")`. Using `strip_option` on a field whose type is not
+//! recognized is an error.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! type MaybeString = Option<String>;
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Lorem {
+//!     #[builder(setter(into, strip_option(inner = "String")))]
+//!     pub ipsum: MaybeString,
+//! }
+//!
+//! fn main() {
+//!     let x = LoremBuilder::default().ipsum("foo").build().unwrap();
+//!
+//!     assert_eq!(x, Lorem {
+//!         ipsum: Some("foo".to_string()),
+//!     });
+//! }
+//! ```
+//!
+//! To also pass an `Option<T>` you already have, use `strip_option(fallback)`: it generates an
+//! additional setter named `maybe_{setter}` which takes the `Option`, or one with a custom name
+//! given by `strip_option(fallback = "foo_opt")`. It honors `setter(into)`, and `try_setter`
+//! gets a `try_` variant of it. Used at the struct level, `fallback` cannot be given a name.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(strip_option(fallback)))]
+//! struct Lorem {
+//!     #[builder(setter(into))]
+//!     pub ipsum: Option<String>,
+//!     #[builder(setter(strip_option(fallback = "foo_opt")))]
+//!     pub foo: Option<u8>,
+//! }
+//!
+//! fn main() {
+//!     let name: Option<&str> = Some("foo");
+//!     let x = LoremBuilder::default()
+//!         .maybe_ipsum(name)
+//!         .foo_opt(None)
+//!         .build()
+//!         .unwrap();
+//!
+//!     assert_eq!(x, Lorem {
+//!         ipsum: Some("foo".to_string()),
+//!         foo: None
+//!     });
+//! }
+//! ```
+//!
+//! With `into`, the type of a bare `None` cannot be inferred, so it needs an annotation such as
+//! `None::<String>`.
+//!
+//! ## Setter Transforms
+//!
+//! With `#[builder(setter(transform = |..| ..))]`, the setter takes the parameters of the
+//! closure and stores the value it returns, so that a setter can take several arguments. Each
+//! parameter needs a name and a type. The setter otherwise behaves like a generated setter,
+//! including its name, visibility, pattern and forwarded attributes, and it can be combined
+//! with `strip_option`, in which case the closure returns the type inside the `Option`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! use std::net::{IpAddr, SocketAddr};
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Lorem {
+//!     #[builder(setter(transform = |ip: [u8; 4], port: u16| SocketAddr::new(IpAddr::from(ip), port)))]
+//!     pub address: SocketAddr,
+//! }
+//!
+//! let x = LoremBuilder::default()
+//!     .address([127, 0, 0, 1], 8080)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(x.address.port(), 8080);
+//! ```
+//!
+//! A transform cannot be combined with `into` on the same field, while a struct-level
+//! `setter(into)` does not apply to it.
 //!
 //! ## Fallible Setters
 //!
@@ -334,6 +421,110 @@
 //! }
 //! ```
 //!
+//! With `#[builder(try_setter(defer))]` on the struct or a field, the fallible setters return
+//! the builder instead of a `Result`, so that they can be chained with the `owned` pattern too.
+//! A failed conversion leaves the field unchanged and is recorded in the builder, as the field
+//! name and the error formatted with `Display`. The build method then returns the first
+//! recorded error as a [`TrySetterError`], or pushes all of them to the [`ValidationErrors`]
+//! with `build_fn(collect_validation_errors)`. The recorded errors of a field are discarded
+//! when it is set or cleared later, and all of them by `reset()`. Adding an item with an
+//! `each` setter keeps them, as the item which failed to convert is still missing.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #[derive(Builder, Debug)]
+//! #[builder(pattern = "owned", try_setter(defer))]
+//! struct Lorem {
+//!     pub ipsum: u8,
+//! }
+//!
+//! let err = LoremBuilder::default()
+//!     .try_ipsum(256)
+//!     .build()
+//!     .unwrap_err();
+//!
+//! assert!(matches!(err, LoremBuilderError::TrySetter(_)));
+//! assert_eq!(
+//!     err.to_string(),
+//!     "`ipsum`: out of range integral type conversion attempted"
+//! );
+//! ```
+//!
+//! A custom error type of the build method must then `impl From<TrySetterError>`. Deferred
+//! errors require the `alloc` feature, and cannot be used with `typestate` or
+//! `build_fn(infallible)`, whose build methods cannot fail.
+//!
+//! ## Getters
+//!
+//! Precede your struct (or field) with `#[builder(getter)]` to generate a `get_foo()` method,
+//! which returns `Option<&T>` with the value of the field if it has been set, and an
+//! `is_foo_set()` method. Fields with a custom builder field type (`field(ty = "...")`) get a
+//! `get_foo()` method which returns a reference to the builder field. Getters have the same
+//! visibility as setters; use `#[builder(getter = false)]` to skip the getter of a field.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(getter)]
+//! struct Lorem {
+//!     ipsum: u32,
+//!     #[builder(default)]
+//!     dolor: u32,
+//! }
+//!
+//! # fn main() {
+//! let mut builder = LoremBuilder::default();
+//! builder.ipsum(42);
+//! assert_eq!(builder.get_ipsum(), Some(&42));
+//! assert!(!builder.is_dolor_set());
+//! # }
+//! ```
+//!
+//! Getters are named after the setter of the field, including its `name` but not its `prefix`,
+//! so the getter of a field with `#[builder(setter(name = "bar"))]` is called `get_bar()`, and
+//! the getter of a field `foo` with `#[builder(setter(prefix = "with"))]` is called `get_foo()`.
+//! The leading underscores are dropped, so the getter of the first field of a tuple struct is
+//! called `get_0()`.
+//!
+//! ## Clearing Fields
+//!
+//! Precede your field with `#[builder(setter(clear))]` to generate a `clear_foo()` method, which
+//! resets the field to its state in an empty builder. It takes and returns the builder like the
+//! setters do. Precede your struct with `#[builder(setter(clear))]` to generate it for all fields,
+//! together with a `reset()` method which clears all fields at once, e.g. to reuse a template
+//! builder. Fields with `#[builder(setter(clear = false))]` are kept by `reset()`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(clear))]
+//! struct Lorem {
+//!     ipsum: u32,
+//!     #[builder(default = "7")]
+//!     dolor: u32,
+//! }
+//!
+//! # fn main() {
+//! let mut builder = LoremBuilder::default();
+//! builder.ipsum(1).dolor(2);
+//! builder.clear_dolor();
+//! assert_eq!(builder.build().unwrap(), Lorem { ipsum: 1, dolor: 7 });
+//!
+//! builder.reset();
+//! assert!(builder.build().is_err());
+//! # }
+//! ```
+//!
+//! Fields with a custom builder field type are reset to its `Default`. Required fields of
+//! [typestate builders](#typestate-builders) have no `clear_foo()` method, so a field-level
+//! `setter(clear)` is an error on them, and `reset()` returns a builder in which they are unset.
+//!
+//! Like getters, the method is named after the setter of the field, including its `name` but
+//! not its `prefix`. `setter(clear)` doesn't enable the setter of a field, so it cannot be used
+//! on a field whose setter is skipped, e.g. with `#[builder(setter(skip))]`.
+//!
 //! ## Default Values
 //!
 //! You can define default values for each field via annotation by `#[builder(default = "...")]`,
@@ -415,6 +606,74 @@
 //! You can even reference other fields, but you have to remember that the builder struct
 //! will wrap every type in an Option ([as illustrated earlier](#what-you-get)).
 //!
+//! ### Optional `Option` Fields
+//!
+//! Fields of type `Option<T>` are required like any other field. With
+//! `#[builder(optional_options)]` on the struct, they default to `None` instead, unless they
+//! have a default or a custom builder field type. The setters of these fields mention it in
+//! their documentation. This combines well with `setter(strip_option)`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(optional_options, setter(into, strip_option))]
+//! struct Lorem {
+//!     ipsum: String,
+//!     dolor: Option<String>,
+//!     #[builder(default = "Some(42)")]
+//!     sit: Option<u32>,
+//! }
+//!
+//! fn main() {
+//!     let x = LoremBuilder::default().ipsum("ipsum").build().unwrap();
+//!
+//!     assert_eq!(x, Lorem {
+//!         ipsum: "ipsum".to_string(),
+//!         dolor: None,
+//!         sit: Some(42),
+//!     });
+//! }
+//! ```
+//!
+//! `Option` is recognized as for `strip_option`, so aliases need `strip_option(inner = "...")`
+//! to default to `None`. With a struct-level `default`, unset fields keep taking their value
+//! from it.
+//!
+//! ## Computed Fields
+//!
+//! A field with `#[builder(computed = "...")]` has no setter, and its value is computed by the
+//! build method from the final values of the other fields, i.e. after defaults are applied.
+//! Every field which isn't computed is in scope as a reference to its value, regardless of
+//! where it is declared, as are the computed fields declared before this one.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(into))]
+//! struct Endpoint {
+//!     host: String,
+//!     #[builder(default = "8080")]
+//!     port: u16,
+//!     #[builder(computed = r#"format!("http://{}:{}", host, port)"#)]
+//!     url: String,
+//! }
+//!
+//! fn main() {
+//!     let x = EndpointBuilder::default().host("localhost").build().unwrap();
+//!
+//!     assert_eq!(x, Endpoint {
+//!         host: "localhost".to_string(),
+//!         port: 8080,
+//!         url: "http://localhost:8080".to_string(),
+//!     });
+//! }
+//! ```
+//!
+//! Computed fields run after the build method's validation and cannot have a default, a setter
+//! or a custom builder field. They are not supported by the builders of functions.
+//!
 //! ## Generic Structs
 //!
 //! ```rust
@@ -432,6 +691,68 @@
 //! }
 //! ```
 //!
+//! ## Enums
+//!
+//! For an enum whose variants have named fields (or no fields), every variant gets its own
+//! builder, e.g. `ShapeCircleBuilder` for `Shape::Circle`. `ShapeBuilder` has an associated
+//! function per variant which returns an empty variant builder. All variant builders use the
+//! options of the enum and return the same error type, `ShapeBuilderError`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(into))]
+//! enum Shape {
+//!     Circle {
+//!         radius: f64,
+//!     },
+//!     Rect {
+//!         width: f64,
+//!         #[builder(default = "1.0")]
+//!         height: f64,
+//!     },
+//! }
+//!
+//! fn main() {
+//!     let x = ShapeBuilder::circle().radius(2.0).build().unwrap();
+//!     assert_eq!(x, Shape::Circle { radius: 2.0 });
+//!
+//!     let err: ShapeBuilderError = ShapeBuilder::rect().height(2.0).build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "`width` must be initialized");
+//! }
+//! ```
+//!
+//! The name of a variant builder can be changed with `#[builder(name = "...")]` on the variant.
+//! Struct-level `default` cannot be used on enums.
+//!
+//! ## Tuple Structs
+//!
+//! Fields of tuple structs are named after their index: the setters and builder fields are
+//! `_0`, `_1`, etc. A setter can be renamed with `#[builder(setter(name = "..."))]`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Debug, PartialEq, Clone, Copy)]
+//! enum Unit {
+//!     Metric,
+//!     Imperial,
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Meters(#[builder(setter(name = "value"))] f64, Unit);
+//!
+//! fn main() {
+//!     let x = MetersBuilder::default().value(2.5)._1(Unit::Metric).build().unwrap();
+//!     assert_eq!(x, Meters(2.5, Unit::Metric));
+//!
+//!     let err = MetersBuilder::default().value(2.5).build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "`_1` must be initialized");
+//! #   let _ = Unit::Imperial;
+//! }
+//! ```
+//!
 //! ## Build Method Customization
 //!
 //! You can rename or suppress the auto-generated build method, leaving you free to implement
@@ -440,6 +761,88 @@
 //!
 //! Additionally if you want to make the build method private you can apply `#[builder(build_fn(private))]`.
 //!
+//! ## Infallible Build Method
+//!
+//! When every field has a default, `#[builder(build_fn(infallible))]` makes the build method
+//! return the struct directly instead of a `Result`, and no error type is generated. A field
+//! has a default if it uses `default` or a custom builder field type, or if the struct uses
+//! `default`. Each field without one is reported as a compile error.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(infallible))]
+//! struct Lorem {
+//!     #[builder(default = "8")]
+//!     ipsum: u8,
+//!     #[builder(default)]
+//!     dolor: Option<String>,
+//! }
+//!
+//! # fn main() {
+//! let x: Lorem = LoremBuilder::default().ipsum(3).build();
+//! assert_eq!(x, Lorem { ipsum: 3, dolor: None });
+//! # }
+//! ```
+//!
+//! `build_fn(infallible)` cannot be combined with `validate`, a custom `error` or
+//! `collect_uninitialized`.
+//!
+//! ## Typestate Builders
+//!
+//! With `#[builder(typestate)]`, the builder keeps track of the required fields in its type,
+//! so forgetting to set one of them is a compile-time error instead of an
+//! `UninitializedField` error at runtime. A field is required unless it has a default
+//! (at field or struct level), is skipped, or uses a custom builder field type.
+//!
+//! The `build` method of a typestate builder only exists once every required field has been
+//! set, and it returns the target struct directly instead of a `Result`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(typestate)]
+//! struct Lorem {
+//!     ipsum: u32,
+//!     #[builder(default = "42")]
+//!     dolor: u32,
+//! }
+//!
+//! fn main() {
+//!     let x: Lorem = LoremBuilder::default().ipsum(1).build();
+//!     assert_eq!(x, Lorem { ipsum: 1, dolor: 42 });
+//! }
+//! ```
+//!
+//! Forgetting to call `ipsum` would not compile:
+//!
+//! ```rust,compile_fail
+//! # use derive_builder::Builder;
+//! #
+//! # #[derive(Builder)]
+//! # #[builder(typestate)]
+//! # struct Lorem {
+//! #     ipsum: u32,
+//! # }
+//! #
+//! # fn main() {
+//! let x: Lorem = LoremBuilder::default().build();
+//! # }
+//! ```
+//!
+//! The builder gets one type parameter per required field, which is either
+//! [`typestate::Unset`] or [`typestate::Set`], e.g. `LoremBuilder<Set>` after `ipsum` was set.
+//!
+//! Note:
+//! * Setters of typestate builders always use the owned pattern.
+//! * `build_fn(validate)`, `build_fn(validate_target)` and `build_fn(error)` cannot be used,
+//!   since the build method cannot fail. The same is true for `default` expressions and `field(build)` blocks
+//!   using `?`.
+//! * Fields with `setter(custom)` need a default, since their setter cannot update the
+//!   typestate.
+//!
 //! ## Pre-Build Validation
 //!
 //! If you're using the provided `build` method, you can declare
@@ -486,6 +889,225 @@
 //!
 //! Note:
 //! * Default values are applied _after_ validation, and will therefore not be validated!
+//!   Use `build_fn(validate_target)` to validate them.
+//!
+//! ## Post-Build Validation
+//!
+//! To check invariants which include defaulted fields, declare
+//! `#[builder(build_fn(validate_target = "path::to::fn"))]`. The function is called with the
+//! constructed target, just before the build method returns it. As with `build_fn(validate)`,
+//! the path is resolved in an `impl` block of the builder, so `Self` refers to the builder.
+//!
+//! The provided function must have the signature `(&Foo) -> Result<_, String>`, or return an
+//! error which converts into the build error like the error of `build_fn(validate)`. It can be
+//! combined with `build_fn(validate)`, which still runs before construction.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(validate_target = "Range::validate"))]
+//! struct Range {
+//!     #[builder(default = "0")]
+//!     start: u32,
+//!     #[builder(default = "10")]
+//!     end: u32,
+//! }
+//!
+//! impl Range {
+//!     fn validate(&self) -> Result<(), String> {
+//!         if self.start > self.end {
+//!             return Err(format!("{} is after {}", self.start, self.end));
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let err = RangeBuilder::default().start(20).build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "20 is after 10");
+//! }
+//! ```
+//!
+//! For function builders, the function is called with the return value of the function.
+//!
+//! ## Field Validation
+//!
+//! Precede a field with `#[builder(validate(...))]` to check its final value, after any default
+//! has been applied. The available validators are:
+//!
+//! * `range(min = ..., max = ...)`: the value must be within the inclusive bounds.
+//! * `non_empty`: the `is_empty()` method of the value must return `false`.
+//! * `len(min = ..., max = ...)`: the `len()` method of the value must return a length within
+//!   the inclusive bounds.
+//! * `with = "path::to::fn"`: a function with the signature `(&T) -> Result<_, String>`.
+//!
+//! Either bound of `range` and `len` can be omitted. Several validators can be combined, and
+//! are checked in the order listed above.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(into))]
+//! struct Lorem {
+//!     #[builder(validate(non_empty, len(max = 16)))]
+//!     ipsum: String,
+//!     #[builder(default = "8080", validate(range(min = 1, max = 65535)))]
+//!     dolor: u32,
+//! }
+//!
+//! # fn main() {
+//! let err = LoremBuilder::default().ipsum("").build().unwrap_err();
+//! assert_eq!(&err.to_string(), "`ipsum`: must not be empty");
+//!
+//! let err = LoremBuilder::default().ipsum("a").dolor(0u32).build().unwrap_err();
+//! assert_eq!(&err.to_string(), "`dolor`: must be at least 1");
+//! # }
+//! ```
+//!
+//! Fields with an `each` setter can additionally check the collection and its items with
+//! `each(name = "...", min = ..., max = ..., validate = "path::to::fn", unique)`:
+//!
+//! * `min` and `max`: the collection must contain at least and at most this many items.
+//! * `validate`: a function which is called with each item, as yielded by iterating over a
+//!   reference to the collection (e.g. `&T` for `Vec<T>`, or `(&K, &V)` for maps), and returns
+//!   `Result<_, String>`.
+//! * `unique`: no item may be equal to an earlier one. Items only need to implement
+//!   `PartialEq`, so each item is compared with every earlier one, which takes quadratic time
+//!   in the number of items. `unique` therefore requires `max`, and is not checked if there
+//!   are more items. Collect into a set type instead for larger collections.
+//!
+//! Failures of an item are reported with its index, e.g. `servers[3]`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! fn check_server(server: &String) -> Result<(), String> {
+//!     if server.is_empty() {
+//!         return Err("must not be empty".to_string());
+//!     }
+//!     Ok(())
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Upstream {
+//!     #[builder(setter(each(name = "server", into, max = 16, validate = "check_server", unique)))]
+//!     servers: Vec<String>,
+//! }
+//!
+//! # fn main() {
+//! let err = UpstreamBuilder::default().server("a").server("").build().unwrap_err();
+//! assert_eq!(&err.to_string(), "`servers[1]`: must not be empty");
+//!
+//! let err = UpstreamBuilder::default().server("a").server("a").build().unwrap_err();
+//! assert_eq!(&err.to_string(), "`servers[1]`: must be unique");
+//! # }
+//! ```
+//!
+//! Failures are reported as a [`FieldValidationError`] with the name of the field. The
+//! generated error type has an additional `FieldValidation` variant; a custom error type must
+//! `impl From<FieldValidationError>`.
+//!
+//! This requires the `alloc` feature.
+//!
+//! ## Reporting All Validation Errors
+//!
+//! By default, the build method returns the first failed validation. With
+//! `#[builder(build_fn(collect_validation_errors))]`, every field validator is checked and all
+//! failures are returned together as a [`ValidationErrors`].
+//!
+//! The pre-build validator then has the signature
+//! `(&FooBuilder, &mut ValidationErrors)` and adds its failures instead of returning them.
+//! Likewise, the post-build validator has the signature `(&Foo, &mut ValidationErrors)`.
+//!
+//! ```rust
+//! # use derive_builder::{Builder, ValidationErrors};
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(into), build_fn(collect_validation_errors, validate = "Self::validate"))]
+//! struct Lorem {
+//!     #[builder(validate(non_empty))]
+//!     ipsum: String,
+//!     #[builder(default = "8080", validate(range(min = 1, max = 65535)))]
+//!     dolor: u32,
+//! }
+//!
+//! impl LoremBuilder {
+//!     fn validate(&self, errors: &mut ValidationErrors) {
+//!         if self.ipsum.as_deref() == Some("dolor") {
+//!             errors.push("ipsum", "must not be \"dolor\"");
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let err = LoremBuilder::default().ipsum("").dolor(0u32).build().unwrap_err();
+//! assert_eq!(
+//!     &err.to_string(),
+//!     "`ipsum`: must not be empty; `dolor`: must be at least 1"
+//! );
+//! # }
+//! ```
+//!
+//! The generated error type has a `ValidationErrors` variant instead of `FieldValidation`; a
+//! custom error type must `impl From<ValidationErrors>`. Uninitialized fields are still
+//! reported immediately.
+//!
+//! This requires the `alloc` feature.
+//!
+//! ## Field Groups
+//!
+//! Declare `#[builder(group(name = "...", <rule>(field, ...)))]` on the struct to check which
+//! fields of a group have been set on the builder. The rule is one of:
+//!
+//! * `exactly_one(...)`: exactly one of the fields must be set.
+//! * `at_least_one(...)`: at least one of the fields must be set.
+//! * `requires(a, ...)`: if `a` is set, all other fields must be set too.
+//!
+//! A field counts as set once its setter has been called, even if it was set to `None`.
+//! Several groups can be declared, and a field can be part of several groups.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(
+//!     setter(into, strip_option),
+//!     group(name = "auth", exactly_one(token, password)),
+//!     group(name = "tls", requires(cert, key))
+//! )]
+//! struct Connection {
+//!     #[builder(default)]
+//!     token: Option<String>,
+//!     #[builder(default)]
+//!     password: Option<String>,
+//!     #[builder(default)]
+//!     cert: Option<String>,
+//!     #[builder(default)]
+//!     key: Option<String>,
+//! }
+//!
+//! # fn main() {
+//! let err = ConnectionBuilder::default().token("a").password("b").build().unwrap_err();
+//! assert_eq!(
+//!     &err.to_string(),
+//!     "group `auth`: exactly one of `token`, `password` must be set, but `token`, `password` are set"
+//! );
+//!
+//! let err = ConnectionBuilder::default().token("a").cert("cert.pem").build().unwrap_err();
+//! assert_eq!(&err.to_string(), "group `tls`: `cert` requires `key` to be set");
+//! # }
+//! ```
+//!
+//! Groups are checked before any validator runs. Violations are reported as a
+//! [`GroupViolationError`], with the name of the group and the fields which were set. The
+//! generated error type has an additional `GroupViolation` variant; a custom error type must
+//! `impl From<GroupViolationError>`. With `build_fn(collect_validation_errors)`, violations are
+//! added to the [`ValidationErrors`] instead, before the errors of the validators.
+//!
+//! Fields with a custom builder field type cannot be part of a group. This requires the
+//! `alloc` feature.
 //!
 //! ## Additional Trait Derivations
 //!
@@ -616,6 +1238,34 @@
 //! # }
 //! ```
 //!
+//! ## Reporting all uninitialized fields
+//!
+//! By default, `build` fails on the first field which has not been set. With
+//! `#[builder(build_fn(collect_uninitialized))]`, every field is checked first and all
+//! missing fields are reported at once as an [`UninitializedFieldsError`]. The generated
+//! error type gets an additional `UninitializedFields` variant; a custom error type must
+//! `impl From<UninitializedFieldsError>`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(collect_uninitialized))]
+//! struct Lorem {
+//!     ipsum: u32,
+//!     dolor: u32,
+//!     #[builder(default)]
+//!     sit: u32,
+//! }
+//!
+//! # fn main() {
+//! let err = LoremBuilder::default().build().unwrap_err();
+//! assert_eq!(&err.to_string(), "Fields not initialized: ipsum, dolor");
+//! # }
+//! ```
+//!
+//! This requires the `alloc` feature.
+//!
 //! # Completely custom fields in the builder
 //!
 //! Instead of having an `Option`, you can have whatever type you like:
@@ -655,6 +1305,226 @@
 //! The argument to `build` must be a literal string containing Rust code for the contents of a block, which must evaluate to the type of the target field.
 //! It may refer to the builder struct as `self`, use `?`, etc.
 //!
+//! # Nested builders
+//!
+//! When the type of a field also derives `Builder`, `#[builder(sub_builder)]` makes the builder
+//! store the field's own builder instead of an `Option` of the field. Instead of a setter, the
+//! builder gets a `foo_mut()` accessor to the nested builder and a `foo_with(|b| ...)` method
+//! which configures it with a closure. The build method builds the field by calling the nested
+//! builder's `build` method. The nested struct opts in with `#[builder(nested)]`, which makes
+//! its generated error type convert into [`SubfieldBuildError`].
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, Clone)]
+//! #[builder(nested)]
+//! struct Tls {
+//!     #[builder(setter(into))]
+//!     cert_path: String,
+//! }
+//!
+//! #[derive(Builder, Debug)]
+//! struct Server {
+//!     port: u16,
+//!     #[builder(sub_builder)]
+//!     tls: Tls,
+//! }
+//!
+//! # fn main() {
+//! let server = ServerBuilder::default()
+//!     .port(443)
+//!     .tls_with(|tls| {
+//!         tls.cert_path("/etc/cert.pem");
+//!     })
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(server.tls.cert_path, "/etc/cert.pem");
+//!
+//! let mut builder = ServerBuilder::default();
+//! builder.port(443);
+//! let err = builder.build().unwrap_err();
+//! assert_eq!(&err.to_string(), "`tls.cert_path` must be initialized");
+//! # }
+//! ```
+//!
+//! Errors of the nested builder are converted into a [`SubfieldBuildError`], which records the
+//! path of the field in which the error occurred, including the fields of all enclosing
+//! builders. The generated error type has an additional `SubfieldBuild` variant; a custom error
+//! type must `impl From<SubfieldBuildError>`. The error type of the nested builder must convert
+//! into `SubfieldBuildError`: a generated error type does with `#[builder(nested)]`, and a custom
+//! error type needs its own `From` implementation.
+//!
+//! As a `sub_builder` field has no setter, it cannot use `setter(into)` or `try_setter`.
+//!
+//! By default, the nested builder type is the field type with `Builder` appended, e.g.
+//! `TlsBuilder` for `Tls`. Use `field(ty = "...")` if it is named differently, and
+//! `field(build = "...")` if its build method is called differently.
+//!
+//! The nested builder is built through a reference unless the outer builder uses the owned
+//! pattern, so the nested builder must not use the owned pattern in that case.
+//!
+//! This requires the `alloc` feature.
+//!
+//! ## Flattened builders
+//!
+//! `#[builder(flatten(setters(...)))]` makes a field a `sub_builder` whose setters are also
+//! available on the outer builder, which forwards them to the nested builder. The macro cannot
+//! see the fields of the nested struct, so the forwarded setters are listed with the type of
+//! their value. This is deliberately narrower than forwarding every setter: a bare
+//! `#[builder(flatten)]` is rejected, and setters which are not listed stay available through
+//! the `_mut()` and `_with()` accessors of the field. Add `prefix = "..."` to avoid collisions
+//! with the other setters of the outer builder.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, Clone)]
+//! #[builder(nested)]
+//! struct RetryPolicy {
+//!     #[builder(default = "3")]
+//!     attempts: u32,
+//! }
+//!
+//! #[derive(Builder, Debug)]
+//! struct Client {
+//!     #[builder(setter(into))]
+//!     url: String,
+//!     #[builder(flatten(prefix = "retry", setters(attempts = "u32")))]
+//!     retry: RetryPolicy,
+//! }
+//!
+//! # fn main() {
+//! let client = ClientBuilder::default()
+//!     .url("https://example.com")
+//!     .retry_attempts(5)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(client.retry.attempts, 5);
+//! # }
+//! ```
+//!
+//! The forwarded setters honor `setter(into)` and the setter prefix of the outer builder. The
+//! nested builder must use the default mutable pattern.
+//!
+//! # Builders from existing values
+//!
+//! `#[builder(from_target)]` implements `From<Lorem>` for `LoremBuilder`, with every field set
+//! to the value of the existing struct. It can then be changed through the setters and built
+//! again, which runs the validation of the build method again.
+//! `#[builder(to_builder)]` additionally adds a `Lorem::to_builder(&self)` method, which
+//! requires the struct to be `Clone`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq, Clone)]
+//! #[builder(to_builder, setter(into))]
+//! struct Lorem {
+//!     ipsum: String,
+//!     #[builder(default = "8080")]
+//!     dolor: u16,
+//!     #[builder(field(
+//!         ty = "Vec<String>",
+//!         build = "self.sit.join(\",\")",
+//!         from_target = "|sit: String| sit.split(',').map(String::from).collect()"
+//!     ))]
+//!     sit: String,
+//! }
+//!
+//! # fn main() {
+//! let lorem = Lorem { ipsum: "lorem".into(), dolor: 80, sit: "a,b".into() };
+//! let changed = lorem.to_builder().dolor(443u16).build().unwrap();
+//! assert_eq!(changed, Lorem { ipsum: "lorem".into(), dolor: 443, sit: "a,b".into() });
+//! # }
+//! ```
+//!
+//! Fields with a custom builder field type need `field(from_target = "...")`, a function or
+//! closure which converts the value of the field into the builder field type. Fields with
+//! `sub_builder` are converted with `From`, so their own builder needs `from_target` as well.
+//! Skipped fields keep their default value when the struct is built again.
+//!
+//! # Builders for functions
+//!
+//! The [`builder`] attribute macro generates a builder for the parameters of a function. Its
+//! build method calls the function with the values set on the builder, and can be renamed with
+//! `build_fn(name = "...")` as usual. The builder is named after the function, e.g.
+//! `ConnectBuilder` for `connect`.
+//!
+//! Options of the builder go on the attribute, and parameters accept the same
+//! `#[builder(...)]` attributes as fields.
+//!
+//! ```rust
+//! #[derive_builder::builder(setter(into))]
+//! fn connect(host: String, #[builder(default = "80")] port: u16) -> String {
+//!     format!("{}:{}", host, port)
+//! }
+//!
+//! # fn main() {
+//! let address = ConnectBuilder::default().host("localhost").build().unwrap();
+//! assert_eq!(address, "localhost:80");
+//! # }
+//! ```
+//!
+//! On an `impl` block, every associated function marked with `#[builder]` gets a builder.
+//! The options of the builder go on the `#[builder]` attribute of the function. The builder of
+//! `new` is named after the type, e.g. `ClientBuilder`; the builders of other functions are
+//! named after both, e.g. `ClientWithProxyBuilder` for `Client::with_proxy`.
+//!
+//! ```rust
+//! # #[derive(Debug, PartialEq)]
+//! struct Client {
+//!     url: String,
+//!     retries: u8,
+//! }
+//!
+//! #[derive_builder::builder]
+//! impl Client {
+//!     #[builder(pattern = "owned", setter(into))]
+//!     pub fn new(url: String, #[builder(default = "3")] retries: u8) -> Self {
+//!         Client { url, retries }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let client = ClientBuilder::default().url("https://example.com").build().unwrap();
+//! assert_eq!(client.retries, 3);
+//! # }
+//! ```
+//!
+//! A method taking `&self` gets a builder which borrows the receiver. The method is replaced
+//! by one with the same name which creates the builder, and the build method calls the
+//! original method. The builder has no `Default` impl.
+//!
+//! ```rust
+//! struct HttpClient {
+//!     base: String,
+//! }
+//!
+//! #[derive_builder::builder]
+//! impl HttpClient {
+//!     #[builder(pattern = "owned", setter(into), build_fn(name = "call"))]
+//!     pub fn send_request(&self, path: String, #[builder(default = "30")] timeout: u32) -> String {
+//!         format!("GET {}{} ({}s)", self.base, path, timeout)
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let client = HttpClient { base: "https://example.com".into() };
+//! let request = client.send_request().path("/").call().unwrap();
+//! assert_eq!(request, "GET https://example.com/ (30s)");
+//! # }
+//! ```
+//!
+//! The build method of the builder of an `async fn` is `async` too. The builder also implements
+//! [`IntoFuture`](core::future::IntoFuture) and uses the owned pattern by default, so it can be
+//! awaited directly, e.g. `client.fetch().url(url).await`. This requires Rust 1.64 and the `alloc` feature. The
+//! future of `IntoFuture` is boxed and not `Send`; if you need a `Send` future, e.g. to spawn
+//! it, await the build method instead.
+//!
+//! Parameters must be plain identifiers, and references must have named lifetimes.
+//! Struct-level `default` cannot be used on functions.
+//!
 //! # **`#![no_std]`** Support (on Nightly)
 //!
 //! You can activate support for `#![no_std]` by adding `#[builder(no_std)]` to your struct
@@ -666,13 +1536,12 @@
 //!
 //! ## Gotchas
 //!
-//! - Tuple structs and unit structs are not supported as they have no field
-//!   names.
+//! - Unit structs are not supported as they have no fields.
 //! - Generic setters introduce a type parameter `VALUE: Into<_>`. Therefore you can't use
 //!   `VALUE` as a type parameter on a generic struct in combination with generic setters.
 //! - The `try_setter` attribute and `owned` builder pattern are not compatible in practice;
 //!   an error during building will consume the builder, making it impossible to continue
-//!   construction.
+//!   construction. Use `try_setter(defer)` to return the error from the build method instead.
 //! - When re-exporting the underlying struct under a different name, the
 //!   auto-generated documentation will not match.
 //! - If derive_builder depends on your crate, and vice versa, then a cyclic
@@ -698,17 +1567,43 @@ extern crate alloc;
 extern crate derive_builder_macro;
 
 mod error;
+pub mod typestate;
 
+pub use derive_builder_macro::builder;
 pub use derive_builder_macro::Builder;
 
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use error::FieldValidationError;
+#[doc(inline)]
+pub use error::GroupRule;
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use error::GroupViolationError;
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use error::SubfieldBuildError;
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use error::TrySetterError;
 #[doc(inline)]
 pub use error::UninitializedFieldError;
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use error::UninitializedFieldsError;
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use error::ValidationErrors;
 
 #[doc(hidden)]
 pub mod export {
     pub mod core {
+        #[cfg(feature = "alloc")]
+        pub use ::alloc::boxed;
         #[cfg(feature = "alloc")]
         pub use ::alloc::string;
+        #[cfg(feature = "alloc")]
+        pub use ::alloc::vec;
         #[cfg(not(feature = "std"))]
         pub use core::*;
         #[cfg(feature = "std")]
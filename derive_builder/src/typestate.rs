@@ -0,0 +1,13 @@
+//! Marker types used by builders generated with `#[builder(typestate)]`.
+//!
+//! A typestate builder has one type parameter per required field. The parameter is
+//! [`Unset`] until the field's setter has been called, after which it becomes [`Set`].
+//! The `build` method is only implemented once every parameter is [`Set`].
+
+/// Type-level marker for a required field whose setter has been called.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Set;
+
+/// Type-level marker for a required field whose setter has not been called yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Unset;
@@ -0,0 +1,133 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(into, clear))]
+struct Lorem {
+    ipsum: String,
+    #[builder(default = "3")]
+    dolor: u32,
+    #[builder(setter(custom), field(ty = "Vec<u8>", build = "self.sit.len()"))]
+    sit: usize,
+    #[builder(setter(clear = false), default)]
+    amet: bool,
+    #[cfg(any())]
+    consectetur: u32,
+}
+
+impl LoremBuilder {
+    fn sit(&mut self, value: u8) -> &mut Self {
+        self.sit.push(value);
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "immutable")]
+struct Immutable {
+    #[builder(setter(clear))]
+    ipsum: u32,
+    #[builder(default)]
+    dolor: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate, setter(clear))]
+struct Point {
+    x: i32,
+    #[builder(default)]
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(clear))]
+struct Pair(u32, #[builder(default)] u32);
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(prefix = "with", clear))]
+struct Dolor {
+    #[builder(setter(name = "sit"), default)]
+    ipsum: u32,
+    #[builder(default)]
+    amet: u32,
+}
+
+#[test]
+fn clear_field() {
+    let mut builder = LoremBuilder::default();
+    builder.ipsum("lorem").dolor(5u32).sit(1).sit(2);
+    builder.clear_dolor().clear_sit();
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Lorem {
+            ipsum: "lorem".to_string(),
+            dolor: 3,
+            sit: 0,
+            amet: false,
+        }
+    );
+
+    builder.clear_ipsum();
+    assert_eq!(
+        &builder.build().unwrap_err().to_string(),
+        "`ipsum` must be initialized"
+    );
+}
+
+#[test]
+fn reset() {
+    let mut template = LoremBuilder::default();
+    template.ipsum("lorem").dolor(5u32).sit(1).amet(true);
+    template.reset();
+
+    template.ipsum("ipsum");
+    assert_eq!(
+        template.build().unwrap(),
+        Lorem {
+            ipsum: "ipsum".to_string(),
+            dolor: 3,
+            sit: 0,
+            // Fields with `setter(clear = false)` are kept
+            amet: true,
+        }
+    );
+}
+
+#[test]
+fn immutable() {
+    let builder = ImmutableBuilder::default().ipsum(1).dolor(2);
+    let cleared = builder.clear_ipsum();
+
+    assert!(cleared.build().is_err());
+    assert_eq!(builder.build().unwrap(), Immutable { ipsum: 1, dolor: 2 });
+}
+
+#[test]
+fn typestate() {
+    let builder = PointBuilder::default().x(1).y(2).clear_y();
+    assert_eq!(builder.build(), Point { x: 1, y: 0 });
+
+    let point = PointBuilder::default().x(1).y(2).reset().x(3).build();
+    assert_eq!(point, Point { x: 3, y: 0 });
+}
+
+#[test]
+fn tuple_struct() {
+    let pair = PairBuilder::default()
+        ._0(1)
+        ._1(2)
+        .clear_1()
+        .build()
+        .unwrap();
+    assert_eq!(pair, Pair(1, 0));
+}
+
+#[test]
+fn renamed_setters() {
+    let mut builder = DolorBuilder::default();
+    builder.sit(1).with_amet(2);
+    builder.clear_sit().clear_amet();
+
+    assert_eq!(builder.build().unwrap(), Dolor { ipsum: 0, amet: 0 });
+}
@@ -0,0 +1,90 @@
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::{UninitializedFieldError, UninitializedFieldsError};
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(collect_uninitialized))]
+struct Lorem {
+    ipsum: u32,
+    dolor: String,
+    #[builder(default)]
+    sit: u8,
+    amet: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum CustomError {
+    Missing(Vec<&'static str>),
+}
+
+impl From<UninitializedFieldError> for CustomError {
+    fn from(e: UninitializedFieldError) -> Self {
+        Self::Missing(vec![e.field_name()])
+    }
+}
+
+impl From<UninitializedFieldsError> for CustomError {
+    fn from(e: UninitializedFieldsError) -> Self {
+        Self::Missing(e.field_names().to_vec())
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(collect_uninitialized, error = "CustomError"))]
+struct Custom {
+    ipsum: u32,
+    dolor: u32,
+}
+
+#[test]
+fn reports_all_missing_fields() {
+    let error = LoremBuilder::default()
+        .dolor("dolor".into())
+        .build()
+        .unwrap_err();
+
+    match error {
+        LoremBuilderError::UninitializedFields(ref e) => {
+            assert_eq!(e.field_names(), &["ipsum", "amet"]);
+        }
+        _ => panic!("unexpected error: {:?}", error),
+    }
+    assert_eq!(&error.to_string(), "Fields not initialized: ipsum, amet");
+}
+
+#[test]
+fn builds_when_all_fields_are_set() {
+    let x = LoremBuilder::default()
+        .ipsum(1)
+        .dolor("dolor".into())
+        .amet(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: 1,
+            dolor: "dolor".into(),
+            sit: 0,
+            amet: true,
+        }
+    );
+}
+
+#[test]
+fn custom_error() {
+    assert_eq!(
+        CustomBuilder::default().build().unwrap_err(),
+        CustomError::Missing(vec!["ipsum", "dolor"])
+    );
+    assert_eq!(
+        CustomBuilder::default().dolor(2).build().unwrap_err(),
+        CustomError::Missing(vec!["ipsum"])
+    );
+    assert_eq!(
+        CustomBuilder::default().ipsum(1).dolor(2).build(),
+        Ok(Custom { ipsum: 1, dolor: 2 })
+    );
+}
@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+struct Lorem {
+    #[builder(setter(skip, clear))]
+    ipsum: u32,
+}
+
+#[derive(Builder)]
+#[builder(setter(skip))]
+struct Dolor {
+    #[builder(setter(clear))]
+    sit: u32,
+    #[builder(setter(into, clear))]
+    amet: u32,
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+struct Sit {
+    #[builder(setter(clear))]
+    amet: u32,
+    #[builder(default, setter(clear))]
+    consectetur: u32,
+}
+
+fn main() {}
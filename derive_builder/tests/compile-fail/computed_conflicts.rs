@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+struct Lorem {
+    ipsum: u8,
+    #[builder(computed = "ipsum * 2", default, setter(into))]
+    dolor: u8,
+}
+
+#[builder]
+fn amet(consectetur: u8, #[builder(computed = "consectetur * 2")] adipiscing: u8) -> u8 {
+    consectetur + adipiscing
+}
+
+fn main() {}
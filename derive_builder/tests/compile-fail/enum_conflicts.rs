@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(default)]
+pub enum Lorem {
+    Ipsum { dolor: u32 },
+}
+
+#[derive(Builder)]
+pub enum Sit {
+    Amet(u32),
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Clone, Builder)]
+#[builder(nested)]
+struct Retry {
+    attempts: u32,
+}
+
+#[derive(Clone, Builder)]
+#[builder(nested, pattern = "owned")]
+struct Tls {
+    verify: bool,
+}
+
+#[derive(Builder)]
+struct Lorem {
+    #[builder(flatten(setters(attempts = "u32")), default)]
+    ipsum: Retry,
+}
+
+#[derive(Builder)]
+struct Dolor {
+    #[builder(flatten(setters(attempts)))]
+    sit: Retry,
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+struct Amet {
+    #[builder(flatten(setters(verify = "bool")))]
+    tls: Tls,
+}
+
+#[derive(Builder)]
+struct Consectetur {
+    #[builder(flatten)]
+    adipiscing: Retry,
+}
+
+fn main() {}
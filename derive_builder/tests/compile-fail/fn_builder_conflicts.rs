@@ -0,0 +1,36 @@
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive_builder::builder(default)]
+fn origin(x: i32) -> i32 {
+    x
+}
+
+#[derive_builder::builder]
+fn distance((x, y): (i32, i32), to: impl Into<i32>) -> i32 {
+    x + y - to.into()
+}
+
+#[derive_builder::builder]
+impl Point {
+    fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+}
+
+#[derive_builder::builder]
+impl Point {
+    #[builder]
+    fn shift(&mut self, dx: i32) {
+        self.x += dx;
+    }
+}
+
+#[derive_builder::builder]
+struct Lorem {
+    ipsum: u32,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(from_target)]
+pub struct Lorem {
+    ipsum: u32,
+    #[builder(field(ty = "Vec<u32>", build = "self.dolor.len()"))]
+    dolor: usize,
+}
+
+#[derive(Builder)]
+#[builder(to_builder)]
+pub enum Sit {
+    Amet { consectetur: u32 },
+}
+
+fn main() {}
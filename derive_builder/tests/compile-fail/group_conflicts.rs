@@ -0,0 +1,35 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(group(name = "auth", exactly_one(token, pasword)))]
+pub struct Lorem {
+    token: String,
+    password: String,
+}
+
+#[derive(Builder)]
+#[builder(group(name = "auth", exactly_one(token), at_least_one(token, password)))]
+pub struct Ipsum {
+    token: String,
+    password: String,
+}
+
+#[derive(Builder)]
+#[builder(group(name = "auth", at_least_one(token, password)))]
+pub struct Dolor {
+    #[builder(field(ty = "Vec<String>", build = "self.token.join(\"\")"))]
+    token: String,
+    password: String,
+}
+
+#[derive(Builder)]
+#[builder(typestate, group(name = "auth", at_least_one(token, password)))]
+pub struct Sit {
+    #[builder(default)]
+    token: String,
+    #[builder(default)]
+    password: String,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(build_fn(infallible))]
+pub struct Lorem {
+    ipsum: u32,
+    #[builder(default)]
+    dolor: u32,
+    sit: String,
+}
+
+#[derive(Builder)]
+#[builder(build_fn(infallible, validate = "Self::check"))]
+pub struct Amet {
+    #[builder(default)]
+    consectetur: u32,
+}
+
+impl AmetBuilder {
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate derive_builder;
+
+type MaybeString = Option<String>;
+
+#[derive(Builder)]
+#[builder(setter(strip_option(inner = "u8")))]
+struct Ipsum {
+    dolor: Option<u8>,
+}
+
+#[derive(Builder)]
+struct Dolor {
+    #[builder(setter(strip_option))]
+    sit: MaybeString,
+    #[builder(setter(strip_option(fallback)))]
+    amet: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(setter(strip_option(fallback = "maybe")))]
+struct Lorem {
+    ipsum: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder, Clone)]
+#[builder(nested)]
+pub struct Inner {
+    value: u32,
+}
+
+#[derive(Builder)]
+pub struct Lorem {
+    #[builder(sub_builder, default)]
+    ipsum: Inner,
+    #[builder(sub_builder)]
+    dolor: (u32, u32),
+    #[builder(sub_builder, setter(each(name = "item")))]
+    sit: Inner,
+    #[builder(sub_builder, setter(into))]
+    amet: Inner,
+    #[builder(sub_builder, try_setter)]
+    consectetur: Inner,
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Ipsum {
+    #[builder(sub_builder)]
+    dolor: Inner,
+}
+
+#[derive(Builder)]
+#[builder(nested, build_fn(error = "String"))]
+pub struct Dolor {
+    sit: u32,
+}
+
+#[derive(Builder)]
+#[builder(nested, build_fn(infallible))]
+pub struct Sit {
+    #[builder(default)]
+    amet: u32,
+}
+
+fn main() {}
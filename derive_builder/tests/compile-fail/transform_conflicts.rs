@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+pub struct Lorem {
+    #[builder(setter(transform = |(x, y): (u32, u32), z| x + y + z))]
+    ipsum: u32,
+    #[builder(setter(into, transform = |value: u8| value.into()))]
+    dolor: u32,
+    #[builder(setter(transform = "not a closure"))]
+    sit: u32,
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(typestate, pattern = "mutable")]
+pub struct Lorem {
+    ipsum: u32,
+}
+
+#[derive(Builder)]
+#[builder(typestate, build_fn(validate = "IpsumBuilder::validate"))]
+pub struct Ipsum {
+    #[builder(pattern = "immutable")]
+    dolor: u32,
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Dolor {
+    #[builder(setter(custom))]
+    sit: u32,
+    #[builder(setter(custom), default)]
+    amet: u32,
+}
+
+#[derive(Builder)]
+#[builder(typestate, try_setter(defer))]
+pub struct Sit {
+    amet: u8,
+}
+
+fn main() {}
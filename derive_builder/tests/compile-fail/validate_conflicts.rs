@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Lorem {
+    #[builder(validate(range(max = 10)))]
+    ipsum: u32,
+}
+
+#[derive(Builder)]
+pub struct Dolor {
+    #[builder(validate(len()))]
+    sit: String,
+}
+
+#[derive(Builder)]
+#[builder(build_fn(infallible, collect_validation_errors))]
+pub struct Amet {
+    #[builder(default)]
+    consectetur: u32,
+}
+
+#[derive(Builder)]
+#[builder(typestate, build_fn(validate_target = "Elit::validate"))]
+pub struct Elit {
+    sed: u32,
+}
+
+impl Elit {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Adipiscing {
+    #[builder(setter(each(name = "elit", max = 4)))]
+    elits: Vec<u32>,
+}
+
+#[derive(Builder)]
+pub struct Sed {
+    #[builder(setter(each(name = "eiusmod", unique)))]
+    eiusmods: Vec<u32>,
+}
+
+fn main() {}
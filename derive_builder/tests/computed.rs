@@ -0,0 +1,157 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(into), from_target)]
+struct Endpoint {
+    host: String,
+    #[builder(default = "8080")]
+    port: u16,
+    #[builder(computed = r#"format!("http://{}:{}", host, port)"#)]
+    url: String,
+    #[builder(computed = "url.len()")]
+    url_len: usize,
+    #[builder(default)]
+    path: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", build_fn(validate = "Self::validate"))]
+struct Range {
+    low: u32,
+    high: u32,
+    #[builder(computed = "high - low")]
+    width: u32,
+}
+
+impl RangeBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match (self.low, self.high) {
+            (Some(low), Some(high)) if low > high => Err("low must not exceed high".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Pair(u8, #[builder(computed = "u16::from(*_0) * 2")] u16);
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate, pattern = "owned")]
+struct Square {
+    side: u32,
+    #[builder(computed = "side * side")]
+    area: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Rectangle {
+    #[builder(computed = "width * height")]
+    area: u32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+enum Shape {
+    Circle {
+        radius: f64,
+        #[builder(computed = "2.0 * radius")]
+        diameter: f64,
+    },
+}
+
+#[test]
+fn computed_from_final_values() {
+    let endpoint = EndpointBuilder::default()
+        .host("localhost")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        endpoint,
+        Endpoint {
+            host: "localhost".to_string(),
+            port: 8080,
+            url: "http://localhost:8080".to_string(),
+            url_len: 21,
+            path: None,
+        }
+    );
+}
+
+#[test]
+fn computed_from_later_fields() {
+    let rectangle = RectangleBuilder::default()
+        .width(2)
+        .height(3)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        rectangle,
+        Rectangle {
+            area: 6,
+            width: 2,
+            height: 3,
+        }
+    );
+}
+
+#[test]
+fn recomputed_from_target() {
+    let endpoint = EndpointBuilder::default()
+        .host("localhost")
+        .build()
+        .unwrap();
+
+    let endpoint = EndpointBuilder::from(endpoint).port(80u16).build().unwrap();
+    assert_eq!(endpoint.url, "http://localhost:80");
+    assert_eq!(endpoint.url_len, 19);
+}
+
+#[test]
+fn uninitialized_dependency() {
+    let err = EndpointBuilder::default().port(80u16).build().unwrap_err();
+    assert_eq!(&err.to_string(), "`host` must be initialized");
+}
+
+#[test]
+fn after_validation() {
+    let range = RangeBuilder::default().low(2).high(5).build().unwrap();
+    assert_eq!(
+        range,
+        Range {
+            low: 2,
+            high: 5,
+            width: 3
+        }
+    );
+
+    let err = RangeBuilder::default().low(5).high(2).build().unwrap_err();
+    assert_eq!(&err.to_string(), "low must not exceed high");
+}
+
+#[test]
+fn tuple_struct() {
+    assert_eq!(PairBuilder::default()._0(21).build().unwrap(), Pair(21, 42));
+}
+
+#[test]
+fn typestate() {
+    assert_eq!(
+        SquareBuilder::default().side(3).build(),
+        Square { side: 3, area: 9 }
+    );
+}
+
+#[test]
+fn enum_variant() {
+    assert_eq!(
+        ShapeBuilder::circle().radius(1.5).build().unwrap(),
+        Shape::Circle {
+            radius: 1.5,
+            diameter: 3.0
+        }
+    );
+}
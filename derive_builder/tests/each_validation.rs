@@ -0,0 +1,222 @@
+#[macro_use]
+extern crate derive_builder;
+
+use std::collections::HashMap;
+
+use derive_builder::FieldValidationError;
+
+fn check_server(server: &String) -> Result<(), String> {
+    if server.contains(':') {
+        Ok(())
+    } else {
+        Err(format!("`{}` has no port", server))
+    }
+}
+
+fn check_label((key, _): (&String, &String)) -> Result<(), String> {
+    if key.is_empty() {
+        Err("key must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(nested)]
+struct Upstream {
+    #[builder(setter(each(
+        name = "server",
+        into,
+        min = 1,
+        max = 3,
+        validate = "check_server",
+        unique
+    )))]
+    servers: Vec<String>,
+    #[builder(
+        default,
+        setter(each(name = "label", key_value, validate = "check_label"))
+    )]
+    labels: HashMap<String, String>,
+    #[builder(default, setter(strip_option, each(name = "tag", max = 2, unique)))]
+    tags: Option<Vec<u32>>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(collect_validation_errors))]
+struct Collected {
+    #[builder(setter(each(name = "server", into, max = 3, validate = "check_server", unique)))]
+    servers: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(error = "MyError"))]
+struct Custom {
+    #[builder(setter(each(name = "item", max = 1)))]
+    items: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+enum MyError {
+    Uninitialized(&'static str),
+    Invalid(String, String),
+}
+
+impl From<derive_builder::UninitializedFieldError> for MyError {
+    fn from(e: derive_builder::UninitializedFieldError) -> Self {
+        Self::Uninitialized(e.field_name())
+    }
+}
+
+impl From<FieldValidationError> for MyError {
+    fn from(e: FieldValidationError) -> Self {
+        Self::Invalid(e.field_path(), e.message().to_string())
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Builder)]
+struct Proxy {
+    #[builder(sub_builder)]
+    upstream: Upstream,
+}
+
+#[test]
+fn valid() {
+    let upstream = UpstreamBuilder::default()
+        .server("a:80")
+        .server("b:80")
+        .label("zone", "eu")
+        .tag(1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        upstream,
+        Upstream {
+            servers: vec!["a:80".to_string(), "b:80".to_string()],
+            labels: vec![("zone".to_string(), "eu".to_string())]
+                .into_iter()
+                .collect(),
+            tags: Some(vec![1]),
+        }
+    );
+}
+
+#[test]
+fn count() {
+    let err = UpstreamBuilder::default()
+        .servers(vec![])
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`servers`: length must be at least 1");
+
+    let err = UpstreamBuilder::default()
+        .server("a:80")
+        .server("b:80")
+        .server("c:80")
+        .server("d:80")
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`servers`: length must be at most 3");
+
+    let err = UpstreamBuilder::default()
+        .server("a:80")
+        .tag(1)
+        .tag(2)
+        .tag(3)
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`tags`: length must be at most 2");
+}
+
+#[test]
+fn items() {
+    let err = UpstreamBuilder::default()
+        .server("a:80")
+        .server("b")
+        .build()
+        .unwrap_err();
+    match err {
+        UpstreamBuilderError::FieldValidation(ref e) => {
+            assert_eq!(e.field_name(), "servers");
+            assert_eq!(e.index(), Some(1));
+            assert_eq!(e.field_path(), "servers[1]");
+            assert_eq!(e.message(), "`b` has no port");
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+    assert_eq!(&err.to_string(), "`servers[1]`: `b` has no port");
+
+    let err = UpstreamBuilder::default()
+        .server("a:80")
+        .label("", "eu")
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`labels[0]`: key must not be empty");
+}
+
+#[test]
+fn unique() {
+    let err = UpstreamBuilder::default()
+        .server("a:80")
+        .server("b:80")
+        .server("a:80")
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`servers[2]`: must be unique");
+
+    let err = UpstreamBuilder::default()
+        .server("a:80")
+        .tag(7)
+        .tag(7)
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`tags[1]`: must be unique");
+}
+
+#[test]
+fn collect_validation_errors() {
+    let err = CollectedBuilder::default()
+        .server("a")
+        .server("b:80")
+        .server("b:80")
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "`servers[0]`: `a` has no port; `servers[2]`: must be unique"
+    );
+
+    // Uniqueness is not checked if there are too many items
+    let err = CollectedBuilder::default()
+        .server("a:80")
+        .server("a:80")
+        .server("a:80")
+        .server("a:80")
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`servers`: length must be at most 3");
+}
+
+#[test]
+fn custom_error() {
+    let err = CustomBuilder::default()
+        .item(1)
+        .item(2)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        MyError::Invalid("items".to_string(), "length must be at most 1".to_string())
+    );
+}
+
+#[test]
+fn sub_builder() {
+    let mut builder = ProxyBuilder::default();
+    builder.upstream_mut().server("a:80").server("a:80");
+
+    let err = builder.build().unwrap_err();
+    assert_eq!(&err.to_string(), "`upstream.servers[1]`: must be unique");
+}
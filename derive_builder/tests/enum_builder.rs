@@ -0,0 +1,129 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+enum Shape {
+    Circle {
+        radius: f64,
+    },
+    Rect {
+        #[builder(setter(into))]
+        width: f64,
+        #[builder(default = "1.0")]
+        height: f64,
+    },
+    Empty,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", try_setter, setter(into))]
+enum Message<'a, T: Clone> {
+    Text {
+        body: &'a str,
+    },
+    Batch {
+        #[builder(setter(each(name = "item")))]
+        items: Vec<T>,
+        priority: u8,
+    },
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+enum Range {
+    #[builder(name = "BoundedRangeBuilder")]
+    Bounded { low: u32, high: u32 },
+}
+
+impl BoundedRangeBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match (self.low, self.high) {
+            (Some(low), Some(high)) if low > high => Err("low must not exceed high".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn variant_builders() {
+    let circle = ShapeBuilder::circle().radius(2.0).build().unwrap();
+    assert_eq!(circle, Shape::Circle { radius: 2.0 });
+
+    let rect = ShapeRectBuilder::default().width(3u8).build().unwrap();
+    assert_eq!(
+        rect,
+        Shape::Rect {
+            width: 3.0,
+            height: 1.0,
+        }
+    );
+
+    assert_eq!(ShapeBuilder::empty().build().unwrap(), Shape::Empty);
+}
+
+#[test]
+fn shared_error_type() {
+    let error: ShapeBuilderError = ShapeBuilder::rect().height(2.0).build().unwrap_err();
+    assert_eq!(&error.to_string(), "`width` must be initialized");
+
+    let error: ShapeBuilderError = ShapeBuilder::circle().build().unwrap_err();
+    assert_eq!(&error.to_string(), "`radius` must be initialized");
+}
+
+#[test]
+fn generic_enum() {
+    let text: Message<u8> = MessageBuilder::text().body("lorem").build().unwrap();
+    assert_eq!(text, Message::Text { body: "lorem" });
+
+    let batch: Message<u8> = MessageBuilder::batch()
+        .item(1)
+        .item(2)
+        .try_priority(3u16)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        batch,
+        Message::Batch {
+            items: vec![1, 2],
+            priority: 3,
+        }
+    );
+}
+
+#[test]
+fn variant_name_and_validation() {
+    let error = RangeBuilder::bounded().low(3).high(1).build().unwrap_err();
+    assert_eq!(&error.to_string(), "low must not exceed high");
+
+    assert_eq!(
+        RangeBuilder::bounded().low(1).high(3).build().unwrap(),
+        Range::Bounded { low: 1, high: 3 }
+    );
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate)]
+enum Endpoint<T> {
+    Tcp {
+        port: u16,
+        #[builder(default)]
+        payload: Option<T>,
+    },
+    Unix,
+}
+
+#[test]
+fn typestate() {
+    let tcp: Endpoint<u8> = EndpointBuilder::tcp().port(80).build();
+    assert_eq!(
+        tcp,
+        Endpoint::Tcp {
+            port: 80,
+            payload: None,
+        }
+    );
+
+    let unix: Endpoint<u8> = EndpointBuilder::unix().build();
+    assert_eq!(unix, Endpoint::Unix);
+}
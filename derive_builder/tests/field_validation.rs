@@ -0,0 +1,144 @@
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::FieldValidationError;
+
+const MAX_NAME_LEN: usize = 8;
+
+fn check_host(host: &str) -> Result<(), String> {
+    if host.contains(' ') {
+        Err("must not contain spaces".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(nested, setter(into))]
+struct Service {
+    #[builder(validate(non_empty, len(max = "MAX_NAME_LEN")))]
+    name: String,
+    #[builder(default = "\"localhost\".to_string()", validate(with = "check_host"))]
+    host: String,
+    #[builder(default = "0", validate(range(min = 1, max = 65535)))]
+    port: u32,
+    #[builder(default, validate(range(min = -1.5)))]
+    weight: f64,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(error = "MyError"))]
+struct Custom {
+    #[builder(validate(len(min = 2)))]
+    tags: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+enum MyError {
+    Uninitialized(&'static str),
+    Invalid(&'static str, String),
+}
+
+impl From<derive_builder::UninitializedFieldError> for MyError {
+    fn from(e: derive_builder::UninitializedFieldError) -> Self {
+        Self::Uninitialized(e.field_name())
+    }
+}
+
+impl From<FieldValidationError> for MyError {
+    fn from(e: FieldValidationError) -> Self {
+        Self::Invalid(e.field_name(), e.message().to_string())
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Builder)]
+struct Deployment {
+    #[builder(sub_builder)]
+    service: Service,
+}
+
+#[test]
+fn valid() {
+    let service = ServiceBuilder::default()
+        .name("api")
+        .port(443u32)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        service,
+        Service {
+            name: "api".to_string(),
+            host: "localhost".to_string(),
+            port: 443,
+            weight: 0.0,
+        }
+    );
+}
+
+#[test]
+fn invalid_set_value() {
+    let mut builder = ServiceBuilder::default();
+    builder.name("").port(443u32);
+    assert_eq!(
+        &builder.build().unwrap_err().to_string(),
+        "`name`: must not be empty"
+    );
+
+    builder.name("a-long-name");
+    assert_eq!(
+        &builder.build().unwrap_err().to_string(),
+        "`name`: length must be at most MAX_NAME_LEN"
+    );
+
+    builder.name("api").host("local host");
+    assert_eq!(
+        &builder.build().unwrap_err().to_string(),
+        "`host`: must not contain spaces"
+    );
+
+    builder.host("localhost").weight(-2.0);
+    assert_eq!(
+        &builder.build().unwrap_err().to_string(),
+        "`weight`: must be at least -1.5"
+    );
+}
+
+#[test]
+fn invalid_default() {
+    let err = ServiceBuilder::default().name("api").build().unwrap_err();
+
+    match err {
+        ServiceBuilderError::FieldValidation(e) => {
+            assert_eq!(e.field_name(), "port");
+            assert_eq!(e.message(), "must be at least 1");
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn custom_error() {
+    let err = CustomBuilder::default().tags(vec![1]).build().unwrap_err();
+    assert_eq!(
+        err,
+        MyError::Invalid("tags", "length must be at least 2".to_string())
+    );
+}
+
+#[test]
+fn sub_builder() {
+    let mut builder = DeploymentBuilder::default();
+    builder.service_mut().name("api");
+
+    let err = builder.build().unwrap_err();
+    match err {
+        DeploymentBuilderError::SubfieldBuild(ref e) => {
+            assert_eq!(e.field_path(), "service.port");
+            assert_eq!(e.message(), Some("must be at least 1"));
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+    assert_eq!(&err.to_string(), "`service.port`: must be at least 1");
+}
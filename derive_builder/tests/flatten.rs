@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate derive_builder;
+
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(nested)]
+struct RetryPolicy {
+    #[builder(default = "3")]
+    attempts: u32,
+    #[builder(setter(into), default = "Duration::from_millis(100)")]
+    backoff: Duration,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(nested)]
+struct Tls {
+    #[builder(setter(into))]
+    cert_path: String,
+    #[builder(default)]
+    verify: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Client {
+    #[builder(setter(into))]
+    url: String,
+    #[builder(flatten(setters(attempts = "u32", backoff = "Duration")))]
+    retry: RetryPolicy,
+    #[builder(flatten(prefix = "tls", setters(cert_path = "String", verify = "bool")))]
+    tls: Tls,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(into, prefix = "with"))]
+struct Server {
+    name: String,
+    #[builder(flatten(prefix = "retry", setters(attempts = "u32")))]
+    retry: RetryPolicy,
+}
+
+#[test]
+fn forwarded_setters() {
+    let client = ClientBuilder::default()
+        .url("https://example.com")
+        .attempts(5)
+        .backoff(Duration::from_secs(1))
+        .tls_cert_path("/etc/cert.pem".to_string())
+        .tls_verify(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        client,
+        Client {
+            url: "https://example.com".to_string(),
+            retry: RetryPolicy {
+                attempts: 5,
+                backoff: Duration::from_secs(1),
+            },
+            tls: Tls {
+                cert_path: "/etc/cert.pem".to_string(),
+                verify: true,
+            },
+        }
+    );
+}
+
+#[test]
+fn sub_builder_accessors() {
+    let mut builder = ClientBuilder::default();
+    builder
+        .url("https://example.com")
+        .tls_with(|tls| {
+            tls.cert_path("/etc/cert.pem");
+        })
+        .retry_mut()
+        .attempts(1);
+
+    let client = builder.build().unwrap();
+    assert_eq!(client.retry.attempts, 1);
+    assert_eq!(client.retry.backoff, Duration::from_millis(100));
+}
+
+#[test]
+fn child_errors() {
+    let err = ClientBuilder::default()
+        .url("https://example.com")
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "`tls.cert_path` must be initialized");
+}
+
+#[test]
+fn prefixes_and_into() {
+    let server = ServerBuilder::default()
+        .with_name("lorem")
+        .with_retry_attempts(2u8)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            name: "lorem".to_string(),
+            retry: RetryPolicy {
+                attempts: 2,
+                backoff: Duration::from_millis(100),
+            },
+        }
+    );
+}
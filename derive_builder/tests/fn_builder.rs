@@ -0,0 +1,290 @@
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+struct Connection {
+    host: String,
+    port: u16,
+    options: Vec<String>,
+}
+
+#[derive_builder::builder(setter(into))]
+fn connect(
+    host: String,
+    #[builder(default = "80")] port: u16,
+    #[builder(default, setter(each(name = "option", into)))] options: Vec<String>,
+) -> Connection {
+    Connection {
+        host,
+        port,
+        options,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Client<T> {
+    name: String,
+    backend: T,
+    retries: u8,
+}
+
+#[derive_builder::builder]
+impl<T> Client<T> {
+    #[builder(pattern = "owned")]
+    fn new(
+        #[builder(setter(into))] name: String,
+        backend: T,
+        #[builder(default)] retries: u8,
+    ) -> Self {
+        Client {
+            name,
+            backend,
+            retries,
+        }
+    }
+
+    #[builder(build_fn(name = "finish"))]
+    fn with_defaults(backend: T) -> Client<T> {
+        Client::new("default".into(), backend, 3)
+    }
+
+    fn backend(&self) -> &T {
+        &self.backend
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Pool<T> {
+    name: String,
+    connections: Vec<T>,
+}
+
+// `T` is only used by the return type
+#[derive_builder::builder]
+impl<T> Pool<T> {
+    #[builder(setter(into))]
+    pub fn new(name: String) -> Self {
+        Pool {
+            name,
+            connections: vec![],
+        }
+    }
+}
+
+#[derive_builder::builder]
+fn parse<T: FromStr>(s: String) -> Option<T> {
+    s.parse().ok()
+}
+
+// The builder stores the reference, so its lifetime must be named
+#[allow(clippy::needless_lifetimes)]
+#[derive_builder::builder(typestate)]
+fn sum<'a>(values: &'a [u32], mut offset: u32) -> u32 {
+    offset += values.iter().sum::<u32>();
+    offset
+}
+
+#[test]
+fn free_fn() {
+    let connection = ConnectBuilder::default()
+        .host("localhost")
+        .option("tcp_nodelay")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        connection,
+        Connection {
+            host: "localhost".into(),
+            port: 80,
+            options: vec!["tcp_nodelay".into()],
+        }
+    );
+
+    // The function itself is still available
+    assert_eq!(connect("ipsum".into(), 8080, vec![]).port, 8080);
+}
+
+#[test]
+fn uninitialized_argument() {
+    let error = ConnectBuilder::default().port(8080u16).build().unwrap_err();
+    assert_eq!(&error.to_string(), "`host` must be initialized");
+}
+
+#[test]
+fn constructor() {
+    let client = ClientBuilder::default()
+        .name("lorem")
+        .backend(42)
+        .build()
+        .unwrap();
+
+    assert_eq!(client.name, "lorem");
+    assert_eq!(*client.backend(), 42);
+    assert_eq!(client.retries, 0);
+}
+
+#[test]
+fn associated_fn() {
+    let client = ClientWithDefaultsBuilder::default()
+        .backend("dolor")
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        client,
+        Client {
+            name: "default".into(),
+            backend: "dolor",
+            retries: 3,
+        }
+    );
+}
+
+#[test]
+fn generics_of_return_type() {
+    let pool: Pool<u32> = PoolBuilder::default().name("lorem").build().unwrap();
+    assert_eq!(
+        pool,
+        Pool {
+            name: "lorem".into(),
+            connections: vec![],
+        }
+    );
+
+    let number = ParseBuilder::<u8>::default()
+        .s("42".into())
+        .build()
+        .unwrap();
+    assert_eq!(number, Some(42));
+}
+
+#[test]
+fn typestate() {
+    let total: u32 = SumBuilder::default().values(&[1, 2, 3]).offset(4).build();
+    assert_eq!(total, 10);
+}
+
+struct HttpClient {
+    base: String,
+}
+
+#[derive_builder::builder]
+impl HttpClient {
+    /// Sends a request relative to the base URL.
+    #[builder(pattern = "owned", setter(into), build_fn(name = "call"))]
+    pub fn send_request(&self, path: String, #[builder(default = "30")] timeout: u32) -> String {
+        format!("{}{} ({}s)", self.base, path, timeout)
+    }
+
+    #[builder(typestate)]
+    fn join<T: ToString>(&self, value: T) -> String {
+        format!("{}{}", self.base, value.to_string())
+    }
+}
+
+#[test]
+fn method() {
+    let client = HttpClient {
+        base: "https://example.com".into(),
+    };
+
+    let response = client.send_request().path("/index.html").call().unwrap();
+    assert_eq!(response, "https://example.com/index.html (30s)");
+
+    let error = client.send_request().timeout(10u32).call().unwrap_err();
+    assert_eq!(&error.to_string(), "`path` must be initialized");
+
+    assert_eq!(client.join().value(42).build(), "https://example.com42");
+}
+
+#[rustversion::since(1.64)]
+mod into_future {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Poll a future which never waits to completion.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Arc::new(NoopWaker).into();
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = Pin::as_mut(&mut future).poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    struct Store {
+        prefix: &'static str,
+    }
+
+    #[derive_builder::builder]
+    impl Store {
+        #[builder(pattern = "owned", setter(into))]
+        async fn fetch(&self, key: String, #[builder(default)] version: u32) -> String {
+            format!("{}/{}@{}", self.prefix, key, version)
+        }
+
+        #[builder(setter(into))]
+        async fn delete(&self, key: String) -> String {
+            format!("{}/{}", self.prefix, key)
+        }
+    }
+
+    #[derive_builder::builder(typestate)]
+    async fn double(value: u32) -> u32 {
+        value * 2
+    }
+
+    async fn yield_now() {}
+
+    #[derive_builder::builder]
+    async fn count(value: u32) -> usize {
+        let shared = Rc::new(value);
+        yield_now().await;
+        Rc::strong_count(&shared)
+    }
+
+    #[test]
+    fn await_method_builder() {
+        let store = Store { prefix: "kv" };
+
+        let value = block_on(async { store.fetch().key("lorem").version(2u32).await });
+        assert_eq!(value.unwrap(), "kv/lorem@2");
+
+        let error = block_on(async { store.fetch().await }).unwrap_err();
+        assert_eq!(&error.to_string(), "`key` must be initialized");
+
+        let value = block_on(store.fetch().key("ipsum").build());
+        assert_eq!(value.unwrap(), "kv/ipsum@0");
+    }
+
+    #[test]
+    fn owned_pattern_by_default() {
+        let store = Store { prefix: "kv" };
+
+        let value = block_on(async { store.delete().key("lorem").await });
+        assert_eq!(value.unwrap(), "kv/lorem");
+    }
+
+    #[test]
+    fn await_fn_builder() {
+        let value = block_on(async { DoubleBuilder::default().value(21).await });
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn await_non_send_future() {
+        let value = block_on(async { CountBuilder::default().value(1).await });
+        assert_eq!(value.unwrap(), 1);
+    }
+}
@@ -0,0 +1,120 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(
+    nested,
+    to_builder,
+    setter(into),
+    build_fn(validate = "Self::validate")
+)]
+struct Config {
+    host: String,
+    #[builder(default = "8080")]
+    port: u16,
+    #[builder(setter(skip))]
+    generation: u32,
+    #[builder(
+        setter(custom),
+        field(
+            ty = "Vec<String>",
+            build = "self.tags.join(\",\")",
+            from_target = "|tags: String| tags.split(',').map(String::from).collect()"
+        )
+    )]
+    tags: String,
+}
+
+impl ConfigBuilder {
+    fn tag(&mut self, tag: &str) -> &mut Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self.port {
+            Some(0) => Err("port must not be 0".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(from_target, pattern = "owned")]
+struct Pair<T>(T, T);
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(to_builder, typestate)]
+struct Point {
+    x: i32,
+    #[builder(default)]
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(from_target)]
+struct Server {
+    #[builder(sub_builder)]
+    config: Config,
+}
+
+fn config() -> Config {
+    ConfigBuilder::default()
+        .host("localhost")
+        .tag("a")
+        .tag("b")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let config = config();
+    assert_eq!(config.to_builder().build().unwrap(), config);
+    assert_eq!(ConfigBuilder::from(config.clone()).build().unwrap(), config);
+}
+
+#[test]
+fn modify_existing() {
+    let config = config();
+    let modified = config.to_builder().port(9090u16).tag("c").build().unwrap();
+
+    assert_eq!(
+        modified,
+        Config {
+            host: "localhost".to_string(),
+            port: 9090,
+            generation: 0,
+            tags: "a,b,c".to_string(),
+        }
+    );
+}
+
+#[test]
+fn revalidate() {
+    let err = config().to_builder().port(0u16).build().unwrap_err();
+    assert_eq!(&err.to_string(), "port must not be 0");
+}
+
+#[test]
+fn tuple_struct() {
+    let pair = PairBuilder::from(Pair(1, 2))._1(3).build().unwrap();
+    assert_eq!(pair, Pair(1, 3));
+}
+
+#[test]
+fn typestate() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(point.to_builder().x(3).build(), Point { x: 3, y: 2 });
+}
+
+#[test]
+fn sub_builder() {
+    let server = Server { config: config() };
+    let mut builder = ServerBuilder::from(server.clone());
+    builder.config_mut().port(9090u16);
+
+    let server = builder.build().unwrap();
+    assert_eq!(server.config.port, 9090);
+    assert_eq!(server.config.host, "localhost");
+}
@@ -0,0 +1,135 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(getter, setter(into))]
+struct Lorem {
+    ipsum: String,
+    #[builder(default)]
+    dolor: Option<u32>,
+    #[builder(setter(skip))]
+    generation: u32,
+    #[builder(field(ty = "Vec<u8>", build = "self.sit.len()"))]
+    sit: usize,
+    #[builder(getter = false)]
+    amet: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Pair(#[builder(getter)] u32, u32);
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate)]
+struct Point {
+    #[builder(getter)]
+    x: i32,
+    #[builder(default)]
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(getter, setter(prefix = "with"))]
+struct Dolor {
+    #[builder(setter(name = "sit"))]
+    ipsum: u32,
+    amet: u32,
+}
+
+mod private {
+    #[allow(dead_code)]
+    #[derive(Debug, Builder)]
+    #[builder(getter)]
+    pub struct Secret {
+        #[builder(private)]
+        value: u32,
+    }
+
+    impl SecretBuilder {
+        pub fn set_value(&mut self, value: u32) -> &mut Self {
+            self.value(value)
+        }
+
+        pub fn value_or_zero(&self) -> u32 {
+            self.get_value().copied().unwrap_or(0)
+        }
+    }
+}
+
+// Would conflict with a generated getter
+impl LoremBuilder {
+    fn get_amet(&self) -> Option<bool> {
+        self.amet
+    }
+}
+
+#[test]
+fn unset_fields() {
+    let builder = LoremBuilder::default();
+
+    assert!(!builder.is_ipsum_set());
+    assert_eq!(builder.get_ipsum(), None);
+    assert!(!builder.is_dolor_set());
+    assert!(builder.get_sit().is_empty());
+}
+
+#[test]
+fn set_fields() {
+    let mut builder = LoremBuilder::default();
+    builder
+        .ipsum("lorem")
+        .dolor(Some(3))
+        .sit(vec![1, 2])
+        .amet(true);
+
+    assert!(builder.is_ipsum_set());
+    assert_eq!(builder.get_ipsum().map(String::as_str), Some("lorem"));
+    assert_eq!(builder.get_dolor(), Some(&Some(3)));
+    assert_eq!(builder.get_sit(), &vec![1, 2]);
+    assert_eq!(builder.get_amet(), Some(true));
+}
+
+#[test]
+fn tuple_struct() {
+    let builder = PairBuilder::default()._0(1);
+
+    assert!(builder.is_0_set());
+    assert_eq!(builder.get_0(), Some(&1));
+}
+
+#[test]
+fn typestate() {
+    let builder = PointBuilder::default();
+    assert!(!builder.is_x_set());
+
+    let builder = builder.x(1);
+    assert_eq!(builder.get_x(), Some(&1));
+}
+
+#[test]
+fn renamed_setters() {
+    let mut builder = DolorBuilder::default();
+    builder.sit(1);
+
+    assert_eq!(builder.get_sit(), Some(&1));
+    assert!(!builder.is_amet_set());
+}
+
+#[test]
+fn prefixed_setter() {
+    let mut builder = DolorBuilder::default();
+    builder.with_amet(2);
+
+    // The prefix only applies to the setter
+    assert_eq!(builder.get_amet(), Some(&2));
+    assert!(builder.is_amet_set());
+}
+
+#[test]
+fn private_getter() {
+    let mut builder = private::SecretBuilder::default();
+    assert_eq!(builder.value_or_zero(), 0);
+
+    builder.set_value(7);
+    assert_eq!(builder.value_or_zero(), 7);
+}
@@ -0,0 +1,237 @@
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::{GroupRule, GroupViolationError};
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    setter(into, strip_option),
+    group(name = "auth", exactly_one(token, password, cert)),
+    group(name = "tls", requires(cert, key)),
+    group(name = "target", at_least_one(host, socket))
+)]
+struct Connection {
+    #[builder(default)]
+    host: Option<String>,
+    #[builder(default)]
+    socket: Option<String>,
+    #[builder(default)]
+    token: Option<String>,
+    #[builder(default)]
+    password: Option<String>,
+    #[builder(default)]
+    cert: Option<String>,
+    #[builder(default)]
+    key: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    build_fn(error = "MyError"),
+    group(name = "size", exactly_one(width, r#height))
+)]
+struct Custom {
+    #[builder(default)]
+    width: u32,
+    #[builder(default)]
+    r#height: u32,
+}
+
+#[derive(Debug, PartialEq)]
+enum MyError {
+    Uninitialized(&'static str),
+    Group(&'static str, Vec<&'static str>),
+}
+
+impl From<derive_builder::UninitializedFieldError> for MyError {
+    fn from(e: derive_builder::UninitializedFieldError) -> Self {
+        Self::Uninitialized(e.field_name())
+    }
+}
+
+impl From<GroupViolationError> for MyError {
+    fn from(e: GroupViolationError) -> Self {
+        Self::Group(e.group(), e.set().to_vec())
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    setter(into),
+    build_fn(collect_validation_errors),
+    group(name = "target", at_least_one(host, socket))
+)]
+struct Collected {
+    #[builder(default)]
+    host: String,
+    #[builder(default)]
+    socket: String,
+    #[builder(default = "8080", validate(range(min = 1)))]
+    port: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    body: Option<String>,
+    form: Option<String>,
+}
+
+#[derive_builder::builder(
+    setter(into, strip_option),
+    group(name = "payload", exactly_one(body, form))
+)]
+fn request(
+    #[builder(default)] body: Option<String>,
+    #[builder(default)] form: Option<String>,
+) -> Request {
+    Request { body, form }
+}
+
+#[test]
+fn valid() {
+    let connection = ConnectionBuilder::default()
+        .host("localhost")
+        .cert("cert.pem")
+        .key("key.pem")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        connection,
+        Connection {
+            host: Some("localhost".to_string()),
+            socket: None,
+            token: None,
+            password: None,
+            cert: Some("cert.pem".to_string()),
+            key: Some("key.pem".to_string()),
+        }
+    );
+}
+
+#[test]
+fn exactly_one() {
+    let err = ConnectionBuilder::default()
+        .host("localhost")
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "group `auth`: exactly one of `token`, `password`, `cert` must be set, but none is set"
+    );
+
+    let err = ConnectionBuilder::default()
+        .host("localhost")
+        .token("secret")
+        .password("hunter2")
+        .build()
+        .unwrap_err();
+    match err {
+        ConnectionBuilderError::GroupViolation(ref e) => {
+            assert_eq!(e.group(), "auth");
+            assert_eq!(e.rule(), GroupRule::ExactlyOne);
+            assert_eq!(e.fields(), &["token", "password", "cert"]);
+            assert_eq!(e.set(), &["token", "password"]);
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+    assert_eq!(
+        &err.to_string(),
+        "group `auth`: exactly one of `token`, `password`, `cert` must be set, but `token`, `password` are set"
+    );
+}
+
+#[test]
+fn requires() {
+    let err = ConnectionBuilder::default()
+        .host("localhost")
+        .cert("cert.pem")
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "group `tls`: `cert` requires `key` to be set"
+    );
+
+    // `key` without `cert` is allowed
+    ConnectionBuilder::default()
+        .host("localhost")
+        .token("secret")
+        .key("key.pem")
+        .build()
+        .unwrap();
+}
+
+#[test]
+fn at_least_one() {
+    let err = ConnectionBuilder::default()
+        .token("secret")
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "group `target`: at least one of `host`, `socket` must be set"
+    );
+
+    ConnectionBuilder::default()
+        .host("localhost")
+        .socket("/run/db.sock")
+        .token("secret")
+        .build()
+        .unwrap();
+}
+
+#[test]
+fn custom_error() {
+    let err = CustomBuilder::default().build().unwrap_err();
+    assert_eq!(err, MyError::Group("size", vec![]));
+
+    let err = CustomBuilder::default()
+        .width(1)
+        .r#height(2)
+        .build()
+        .unwrap_err();
+    assert_eq!(err, MyError::Group("size", vec!["width", "height"]));
+
+    let custom = CustomBuilder::default().width(1).build().unwrap();
+    assert_eq!(
+        custom,
+        Custom {
+            width: 1,
+            r#height: 0
+        }
+    );
+}
+
+#[test]
+fn collect_validation_errors() {
+    let err = CollectedBuilder::default().port(0u32).build().unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "group `target`: at least one of `host`, `socket` must be set; `port`: must be at least 1"
+    );
+
+    let collected = CollectedBuilder::default()
+        .host("localhost")
+        .build()
+        .unwrap();
+    assert_eq!(collected.port, 8080);
+}
+
+#[test]
+fn function() {
+    let err = RequestBuilder::default().build().unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "group `payload`: exactly one of `body`, `form` must be set, but none is set"
+    );
+
+    let request = RequestBuilder::default().body("{}").build().unwrap();
+    assert_eq!(
+        request,
+        Request {
+            body: Some("{}".to_string()),
+            form: None,
+        }
+    );
+}
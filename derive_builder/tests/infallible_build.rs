@@ -0,0 +1,86 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(infallible), setter(into))]
+struct Options {
+    #[builder(default = "4")]
+    workers: u8,
+    #[builder(default)]
+    name: String,
+    #[builder(setter(skip))]
+    generation: u32,
+    #[builder(
+        setter(custom),
+        field(ty = "Vec<u16>", build = "self.max_port.iter().copied().max()")
+    )]
+    max_port: Option<u16>,
+}
+
+impl OptionsBuilder {
+    fn port(&mut self, port: u16) -> &mut Self {
+        self.max_port.push(port);
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Default, Clone, Builder)]
+#[builder(default, pattern = "owned", build_fn(infallible, name = "finish"))]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(infallible))]
+enum Shape {
+    Circle {
+        #[builder(default = "1.0")]
+        radius: f64,
+    },
+    Empty,
+}
+
+#[test]
+fn defaults() {
+    let options: Options = OptionsBuilder::default().build();
+
+    assert_eq!(
+        options,
+        Options {
+            workers: 4,
+            name: String::new(),
+            generation: 0,
+            max_port: None,
+        }
+    );
+}
+
+#[test]
+fn set_fields() {
+    let options = OptionsBuilder::default()
+        .workers(2)
+        .name("lorem")
+        .port(80)
+        .port(443)
+        .build();
+
+    assert_eq!(options.workers, 2);
+    assert_eq!(options.name, "lorem");
+    assert_eq!(options.max_port, Some(443));
+}
+
+#[test]
+fn struct_default() {
+    let point: Point = PointBuilder::default().y(3).finish();
+    assert_eq!(point, Point { x: 0, y: 3 });
+}
+
+#[test]
+fn enum_variants() {
+    assert_eq!(
+        ShapeBuilder::circle().build(),
+        Shape::Circle { radius: 1.0 }
+    );
+    assert_eq!(ShapeBuilder::empty().build(), Shape::Empty);
+}
@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(optional_options, setter(into))]
+struct Lorem {
+    ipsum: String,
+    dolor: Option<String>,
+    #[builder(default = "Some(3)")]
+    sit: Option<u8>,
+    #[builder(setter(strip_option))]
+    amet: Option<u8>,
+    #[builder(field(ty = "Option<u8>", build = "self.consectetur.or(Some(1))"))]
+    consectetur: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(optional_options, setter(strip_option), pattern = "owned", typestate)]
+struct Ipsum {
+    dolor: u8,
+    sit: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(optional_options, default)]
+struct Dolor {
+    sit: Option<u8>,
+}
+
+impl Default for Dolor {
+    fn default() -> Self {
+        Self { sit: Some(1) }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(optional_options, build_fn(infallible))]
+struct Sit(#[builder(setter(strip_option))] Option<u8>);
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(optional_options)]
+enum Amet {
+    Consectetur { adipiscing: Option<u8> },
+}
+
+#[test]
+fn unset_options_default_to_none() {
+    let lorem = LoremBuilder::default().ipsum("ipsum").build().unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: "ipsum".to_string(),
+            dolor: None,
+            sit: Some(3),
+            amet: None,
+            consectetur: Some(1),
+        }
+    );
+}
+
+#[test]
+fn other_fields_are_required() {
+    assert_eq!(
+        &LoremBuilder::default().build().unwrap_err().to_string(),
+        "`ipsum` must be initialized"
+    );
+}
+
+#[test]
+fn set_options() {
+    let lorem = LoremBuilder::default()
+        .ipsum("ipsum")
+        .dolor(Some("dolor".to_string()))
+        .amet(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(lorem.dolor, Some("dolor".to_string()));
+    assert_eq!(lorem.amet, Some(2));
+}
+
+#[test]
+fn typestate() {
+    let ipsum = IpsumBuilder::default().dolor(1).build();
+    assert_eq!(
+        ipsum,
+        Ipsum {
+            dolor: 1,
+            sit: None
+        }
+    );
+}
+
+#[test]
+fn struct_default() {
+    let dolor = DolorBuilder::default().build().unwrap();
+    assert_eq!(dolor, Dolor { sit: Some(1) });
+}
+
+#[test]
+fn infallible() {
+    assert_eq!(SitBuilder::default().build(), Sit(None));
+    assert_eq!(SitBuilder::default()._0(1).build(), Sit(Some(1)));
+}
+
+#[test]
+fn enum_builder() {
+    assert_eq!(
+        AmetBuilder::consectetur().build().unwrap(),
+        Amet::Consectetur { adipiscing: None }
+    );
+}
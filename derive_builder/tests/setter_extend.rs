@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate derive_builder;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, PartialEq, Default, Builder, Clone)]
 struct Lorem {
@@ -156,3 +156,157 @@ fn extend_field_enabled() {
     let x = SitBuilder::default().foo(1).foo(2).build().unwrap();
     assert_eq!(x, Sit { foos: vec![1, 2] });
 }
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+#[builder(pattern = "owned")]
+struct Amet {
+    #[builder(setter(each(name = "header", key_value)))]
+    headers: HashMap<String, String>,
+    #[builder(setter(each(name = "limit", key_value)))]
+    limits: BTreeMap<&'static str, u64>,
+    #[builder(setter(strip_option, each(name = "label", key_value)))]
+    labels: Option<HashMap<String, i32>>,
+    #[builder(setter(each(name = "param", key_value)))]
+    params: Vec<(String, String)>,
+}
+
+#[test]
+fn extend_field_key_value() {
+    let x = AmetBuilder::default()
+        .header("accept", "*/*")
+        .header(String::from("host"), "localhost")
+        .limit("connections", 10u32)
+        .limit("requests", 100u8)
+        .label("zone", 3)
+        .param("q", "rust")
+        .param("page", "2")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Amet {
+            headers: vec![
+                ("accept".into(), "*/*".into()),
+                ("host".into(), "localhost".into())
+            ]
+            .into_iter()
+            .collect(),
+            limits: vec![("connections", 10), ("requests", 100)]
+                .into_iter()
+                .collect(),
+            labels: Some(vec![("zone".into(), 3)].into_iter().collect()),
+            params: vec![("q".into(), "rust".into()), ("page".into(), "2".into())],
+        }
+    );
+}
+
+#[test]
+fn extend_field_bulk() {
+    let x = DolorBuilder::default()
+        .bar("bar")
+        .extend_bars(vec!["bar bar", "bar bar bar"])
+        .extend_foo("foo-foo".chars())
+        .extend_bazes(vec![("baz".into(), 1), ("bazz".into(), 2)])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Dolor {
+            foo: "foo-foo".into(),
+            bars: vec!["bar".into(), "bar bar".into(), "bar bar bar".into()],
+            bazes: vec![("baz".into(), 1), ("bazz".into(), 2)]
+                .into_iter()
+                .collect(),
+        }
+    );
+
+    let x = IpsumBuilder::default()
+        .extend_foo(None::<char>)
+        .extend_bars(None)
+        .extend_bazes(None)
+        .extend_quxes(vec!["qux".to_string()])
+        .qux("qux qux".into())
+        .extend_quuxes(Some(("quux".to_string(), 1)))
+        .build()
+        .unwrap();
+    assert_eq!(x.quxes, Some(vec!["qux".into(), "qux qux".into()]));
+    assert_eq!(
+        x.quuxes,
+        Some(vec![("quux".into(), 1)].into_iter().collect())
+    );
+
+    let x = AmetBuilder::default()
+        .header("accept", "*/*")
+        .extend_headers(vec![("host", "localhost")])
+        .extend_limits(vec![("connections", 10u32)])
+        .extend_labels(None::<(String, i32)>)
+        .extend_params(vec![("q", "rust")])
+        .build()
+        .unwrap();
+    assert_eq!(
+        x.headers,
+        vec![
+            ("accept".into(), "*/*".into()),
+            ("host".into(), "localhost".into())
+        ]
+        .into_iter()
+        .collect()
+    );
+    assert_eq!(x.limits, vec![("connections", 10)].into_iter().collect());
+}
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+#[builder(try_setter, setter(into))]
+struct Consectetur {
+    #[builder(setter(each(name = "byte")))]
+    bytes: Vec<u8>,
+    #[builder(setter(strip_option, each(name = "port")))]
+    ports: Option<Vec<u16>>,
+}
+
+#[test]
+fn try_extend_field() {
+    let mut builder = ConsecteturBuilder::default();
+    builder.try_byte(1i32).unwrap().try_byte(2u64).unwrap();
+    builder.try_port(8080i32).unwrap();
+
+    assert!(builder.try_byte(256i32).is_err());
+    assert!(builder.try_port(-1i32).is_err());
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Consectetur {
+            bytes: vec![1, 2],
+            ports: Some(vec![8080]),
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+#[builder(setter(prefix = "with"))]
+struct Adipiscing {
+    #[builder(setter(each = "tag"))]
+    tags: Vec<String>,
+    #[builder(setter(name = "ports", each = "port"))]
+    listen: Vec<u16>,
+}
+
+#[test]
+fn extend_named_after_setter() {
+    let x = AdipiscingBuilder::default()
+        .extend_with_tags(vec!["lorem".to_string()])
+        .tag("ipsum".to_string())
+        .extend_ports(vec![80, 443])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Adipiscing {
+            tags: vec!["lorem".to_string(), "ipsum".to_string()],
+            listen: vec![80, 443],
+        }
+    );
+}
@@ -22,6 +22,47 @@ struct Ipsum {
     strip_opt_with_default: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(into, strip_option(fallback)), try_setter)]
+struct Dolor {
+    #[builder(default)]
+    sit: Option<String>,
+    #[builder(setter(strip_option(fallback = "amet_opt")), default)]
+    amet: Option<u8>,
+    #[builder(setter(strip_option = false), default)]
+    consectetur: Option<u8>,
+    #[builder(setter(name = "set_adipiscing"), default)]
+    _adipiscing: Option<u16>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", try_setter(defer))]
+struct Elit {
+    #[builder(setter(strip_option(fallback)))]
+    sed: Option<u8>,
+}
+
+type MaybeString = Option<String>;
+type MaybeBytes = std::option::Option<Vec<u8>>;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(into))]
+struct Adipiscing {
+    #[builder(setter(strip_option(inner = "String", fallback)), default)]
+    elit: MaybeString,
+    #[builder(setter(strip_option(inner = "Vec<u8>"), each(name = "byte")), default)]
+    sed: MaybeBytes,
+    #[builder(setter(strip_option = false))]
+    do_: MaybeString,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(optional_options)]
+struct Eiusmod {
+    #[builder(setter(strip_option(inner = "String")))]
+    tempor: MaybeString,
+}
+
 #[test]
 fn generic_field() {
     let x = LoremBuilder::default()
@@ -60,3 +101,107 @@ fn generic_struct() {
         }
     );
 }
+
+#[test]
+fn fallback() {
+    let dolor = DolorBuilder::default()
+        .maybe_sit(Some("sit"))
+        .amet_opt(Some(1))
+        .consectetur(Some(2))
+        .maybe_set_adipiscing(Some(3u8))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        dolor,
+        Dolor {
+            sit: Some("sit".to_string()),
+            amet: Some(1),
+            consectetur: Some(2),
+            _adipiscing: Some(3),
+        }
+    );
+
+    let dolor = DolorBuilder::default()
+        .sit("sit")
+        .maybe_sit(None::<String>)
+        .amet(1)
+        .amet_opt(None::<u8>)
+        .build()
+        .unwrap();
+
+    assert_eq!(dolor.sit, None);
+    assert_eq!(dolor.amet, None);
+}
+
+#[test]
+fn try_fallback() {
+    let mut builder = DolorBuilder::default();
+    builder
+        .try_maybe_set_adipiscing(Some(1000i32))
+        .unwrap()
+        .try_amet_opt(None::<i32>)
+        .unwrap();
+    assert!(builder.try_amet_opt(Some(-1i32)).is_err());
+
+    let dolor = builder.build().unwrap();
+    assert_eq!(dolor._adipiscing, Some(1000));
+    assert_eq!(dolor.amet, None);
+}
+
+#[test]
+fn try_fallback_defer() {
+    let elit = ElitBuilder::default()
+        .try_maybe_sed(Some(1i32))
+        .build()
+        .unwrap();
+    assert_eq!(elit, Elit { sed: Some(1) });
+
+    let elit = ElitBuilder::default().maybe_sed(None).build().unwrap();
+    assert_eq!(elit, Elit { sed: None });
+
+    assert!(ElitBuilder::default()
+        .try_maybe_sed(Some(256i32))
+        .build()
+        .is_err());
+}
+
+#[test]
+fn alias() {
+    let adipiscing = AdipiscingBuilder::default()
+        .elit("elit")
+        .byte(1)
+        .byte(2)
+        .do_(None)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        adipiscing,
+        Adipiscing {
+            elit: Some("elit".to_string()),
+            sed: Some(vec![1, 2]),
+            do_: None,
+        }
+    );
+
+    let adipiscing = AdipiscingBuilder::default()
+        .maybe_elit(None::<String>)
+        .do_(Some("do".to_string()))
+        .build()
+        .unwrap();
+    assert_eq!(adipiscing.elit, None);
+    assert_eq!(adipiscing.do_, Some("do".to_string()));
+}
+
+#[test]
+fn alias_optional_options() {
+    let eiusmod = EiusmodBuilder::default().build().unwrap();
+    assert_eq!(eiusmod.tempor, None);
+
+    let eiusmod = EiusmodBuilder::default()
+        .tempor("tempor".into())
+        .build()
+        .unwrap();
+    assert_eq!(eiusmod.tempor, Some("tempor".to_string()));
+}
@@ -0,0 +1,109 @@
+#[macro_use]
+extern crate derive_builder;
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(into))]
+struct Lorem {
+    #[builder(setter(transform = |ip: [u8; 4], port: u16| SocketAddr::new(IpAddr::from(ip), port)))]
+    address: SocketAddr,
+    #[builder(setter(
+        name = "timeout_secs",
+        transform = "|secs: u64| Duration::from_secs(secs)"
+    ))]
+    timeout: Duration,
+    #[builder(setter(strip_option, transform = |name: &str| name.to_uppercase()), default)]
+    name: Option<String>,
+    #[builder(setter(transform = || 3))]
+    retries: u32,
+    label: String,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Ipsum {
+    #[builder_setter_attr(must_use)]
+    #[builder(setter(transform = |mut parts: Vec<&str>, sep: char| {
+        parts.retain(|part| !part.is_empty());
+        parts.join(&sep.to_string())
+    }))]
+    path: String,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate)]
+struct Point {
+    #[builder(setter(transform = |x: i32, y: i32| (x, y)))]
+    coordinates: (i32, i32),
+}
+
+mod private {
+    #[derive(Debug, Builder)]
+    pub struct Dolor {
+        #[builder(private, setter(transform = |value: u8| value * 2))]
+        pub sit: u8,
+    }
+
+    impl DolorBuilder {
+        pub fn set_sit(&mut self, value: u8) -> &mut Self {
+            self.sit(value)
+        }
+    }
+}
+
+#[test]
+fn transform() {
+    let lorem = LoremBuilder::default()
+        .address([127, 0, 0, 1], 8080)
+        .timeout_secs(30)
+        .name("lorem")
+        .retries()
+        .label("ipsum")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            address: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8080),
+            timeout: Duration::from_secs(30),
+            name: Some("LOREM".to_string()),
+            retries: 3,
+            label: "ipsum".to_string(),
+        }
+    );
+}
+
+#[test]
+fn owned() {
+    let ipsum = IpsumBuilder::default()
+        .path(vec!["", "usr", "", "lib"], '/')
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        ipsum,
+        Ipsum {
+            path: "usr/lib".to_string()
+        }
+    );
+}
+
+#[test]
+fn typestate() {
+    let point = PointBuilder::default().coordinates(1, 2).build();
+    assert_eq!(
+        point,
+        Point {
+            coordinates: (1, 2)
+        }
+    );
+}
+
+#[test]
+fn private_setter() {
+    let dolor = private::DolorBuilder::default().set_sit(4).build().unwrap();
+    assert_eq!(dolor.sit, 8);
+}
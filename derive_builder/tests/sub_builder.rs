@@ -0,0 +1,136 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(nested, build_fn(validate = "Self::validate"))]
+struct Tls {
+    #[builder(setter(into))]
+    cert_path: String,
+    #[builder(default)]
+    verify: bool,
+}
+
+impl TlsBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.cert_path {
+            Some(ref path) if path.is_empty() => Err("empty certificate path".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(nested)]
+struct Server {
+    #[builder(setter(into))]
+    name: String,
+    #[builder(sub_builder)]
+    tls: Tls,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Config {
+    #[builder(sub_builder)]
+    server: Server,
+    #[builder(default = "8")]
+    workers: u8,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(nested, name = "PortRangeConfigurator", pattern = "owned")]
+struct PortRange {
+    low: u16,
+    high: u16,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Listener {
+    #[builder(sub_builder, field(ty = "PortRangeConfigurator"))]
+    ports: PortRange,
+}
+
+#[test]
+fn mut_accessor() {
+    let mut builder = ConfigBuilder::default();
+    builder.server_mut().name("lorem");
+    builder.server_mut().tls_mut().cert_path("/etc/cert.pem");
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Config {
+            server: Server {
+                name: "lorem".into(),
+                tls: Tls {
+                    cert_path: "/etc/cert.pem".into(),
+                    verify: false,
+                },
+            },
+            workers: 8,
+        }
+    );
+}
+
+#[test]
+fn closure_setter() {
+    let config = ConfigBuilder::default()
+        .server_with(|server| {
+            server.name("lorem").tls_with(|tls| {
+                tls.cert_path("/etc/cert.pem").verify(true);
+            });
+        })
+        .workers(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.server.tls.cert_path, "/etc/cert.pem");
+    assert!(config.server.tls.verify);
+    assert_eq!(config.workers, 2);
+}
+
+#[test]
+fn uninitialized_nested_field() {
+    let error = ConfigBuilder::default()
+        .server_with(|server| {
+            server.name("lorem");
+        })
+        .build()
+        .unwrap_err();
+
+    match error {
+        ConfigBuilderError::SubfieldBuild(ref e) => {
+            assert_eq!(e.field_path(), "server.tls.cert_path");
+            assert!(e.is_uninitialized());
+        }
+        _ => panic!("unexpected error: {:?}", error),
+    }
+    assert_eq!(
+        &error.to_string(),
+        "`server.tls.cert_path` must be initialized"
+    );
+}
+
+#[test]
+fn nested_validation_error() {
+    let error = ConfigBuilder::default()
+        .server_with(|server| {
+            server.name("lorem").tls_mut().cert_path("");
+        })
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&error.to_string(), "`server.tls`: empty certificate path");
+}
+
+#[test]
+fn custom_builder_type() {
+    let listener = ListenerBuilder::default()
+        .ports_with(|ports| *ports = PortRangeConfigurator::default().low(80).high(90))
+        .build()
+        .unwrap();
+
+    assert_eq!(listener.ports, PortRange { low: 80, high: 90 });
+
+    let error = ListenerBuilder::default().build().unwrap_err();
+    assert_eq!(&error.to_string(), "`ports.low` must be initialized");
+}
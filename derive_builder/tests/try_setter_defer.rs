@@ -0,0 +1,194 @@
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::TrySetterError;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", try_setter(defer), setter(into))]
+struct Lorem {
+    port: u16,
+    #[builder(default)]
+    retries: u8,
+    #[builder(setter(strip_option), default)]
+    timeout: Option<u32>,
+    #[builder(setter(each(name = "weight")), default)]
+    weights: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(nested, getter)]
+struct Ipsum {
+    #[builder(try_setter(defer))]
+    dolor: u8,
+    #[builder(try_setter, default)]
+    sit: u8,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(try_setter(defer), setter(clear), build_fn(collect_validation_errors))]
+struct Amet {
+    #[builder(default)]
+    consectetur: u8,
+    #[builder(default)]
+    adipiscing: i8,
+}
+
+#[derive(Debug, Builder)]
+#[allow(dead_code)]
+struct Elit {
+    #[builder(sub_builder)]
+    ipsum: Ipsum,
+}
+
+#[test]
+fn converted() {
+    let lorem = LoremBuilder::default()
+        .try_port(8080i32)
+        .try_retries(3u64)
+        .try_timeout(30i64)
+        .try_weight(1i32)
+        .try_weight(2i32)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            port: 8080,
+            retries: 3,
+            timeout: Some(30),
+            weights: vec![1, 2],
+        }
+    );
+}
+
+#[test]
+fn first_error_is_returned() {
+    let err = LoremBuilder::default()
+        .try_port(-1i32)
+        .try_retries(1000u64)
+        .build()
+        .unwrap_err();
+
+    match err {
+        LoremBuilderError::TrySetter(ref e) => {
+            assert_eq!(e.field_name(), "port");
+            assert_eq!(
+                e.message(),
+                "out of range integral type conversion attempted"
+            );
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+    assert_eq!(
+        &err.to_string(),
+        "`port`: out of range integral type conversion attempted"
+    );
+}
+
+#[test]
+fn failed_setter_keeps_value() {
+    let mut builder = IpsumBuilder::default();
+    builder.dolor(1).try_dolor(256);
+    assert_eq!(builder.get_dolor(), Some(&1));
+
+    // Only `dolor` defers its errors
+    assert!(builder.try_sit(256).is_err());
+
+    assert!(matches!(
+        builder.build(),
+        Err(IpsumBuilderError::TrySetter(_))
+    ));
+}
+
+#[test]
+fn set_field_discards_errors() {
+    let mut builder = IpsumBuilder::default();
+    builder.try_dolor(300).dolor(3);
+    assert_eq!(builder.build().unwrap(), Ipsum { dolor: 3, sit: 0 });
+
+    builder.try_dolor(300).try_dolor(4);
+    assert_eq!(builder.build().unwrap(), Ipsum { dolor: 4, sit: 0 });
+
+    let mut builder = AmetBuilder::default();
+    builder.try_consectetur(-1).try_adipiscing(128);
+    builder.clear_consectetur();
+    match builder.build().unwrap_err() {
+        AmetBuilderError::ValidationErrors(ref e) => {
+            assert_eq!(
+                e.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+                ["adipiscing"]
+            );
+        }
+        err => panic!("unexpected error {}", err),
+    }
+
+    // Adding an item does not replace the item which failed to convert
+    let err = LoremBuilder::default()
+        .port(80u16)
+        .try_weight(256i32)
+        .weight(1u8)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "`weights`: out of range integral type conversion attempted"
+    );
+}
+
+#[test]
+fn collect_validation_errors() {
+    let err = AmetBuilder::default()
+        .try_consectetur(-1)
+        .try_adipiscing(128)
+        .build()
+        .unwrap_err();
+
+    match err {
+        AmetBuilderError::ValidationErrors(ref e) => {
+            assert_eq!(e.len(), 2);
+            assert_eq!(
+                e.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+                ["consectetur", "adipiscing"]
+            );
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn reset() {
+    let mut builder = AmetBuilder::default();
+    builder.try_consectetur(-1);
+    assert!(builder.build().is_err());
+
+    builder.reset().try_consectetur(1);
+    assert_eq!(
+        builder.build().unwrap(),
+        Amet {
+            consectetur: 1,
+            adipiscing: 0,
+        }
+    );
+}
+
+#[test]
+fn sub_builder() {
+    let mut builder = ElitBuilder::default();
+    builder.ipsum_mut().try_dolor(-1);
+
+    let err = builder.build().unwrap_err();
+    match err {
+        ElitBuilderError::SubfieldBuild(ref e) => {
+            assert_eq!(e.field_path(), "ipsum.dolor");
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn error_type() {
+    let error = TrySetterError::new("port", "invalid port");
+    assert_eq!(error.field_name(), "port");
+    assert_eq!(&error.to_string(), "`port`: invalid port");
+}
@@ -0,0 +1,62 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Unit {
+    Metric,
+    Imperial,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Meters(#[builder(setter(name = "value"))] f64, Unit);
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(into))]
+struct Label(String, #[builder(default = "1")] u8);
+
+#[derive(Debug, PartialEq, Default, Builder)]
+#[builder(default)]
+struct Point(i32, i32, i32);
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate)]
+struct Wrapper<T>(T);
+
+#[test]
+fn renamed_setter() {
+    let meters = MetersBuilder::default()
+        .value(2.5)
+        ._1(Unit::Imperial)
+        .build()
+        .unwrap();
+
+    assert_eq!(meters, Meters(2.5, Unit::Imperial));
+}
+
+#[test]
+fn uninitialized_field() {
+    let error = MetersBuilder::default()
+        ._1(Unit::Metric)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&error.to_string(), "`_0` must be initialized");
+}
+
+#[test]
+fn field_default() {
+    let label = LabelBuilder::default()._0("lorem").build().unwrap();
+    assert_eq!(label, Label("lorem".into(), 1));
+}
+
+#[test]
+fn struct_default() {
+    let point = PointBuilder::default()._1(4).build().unwrap();
+    assert_eq!(point, Point(0, 4, 0));
+}
+
+#[test]
+fn typestate_newtype() {
+    let wrapper: Wrapper<&str> = WrapperBuilder::default()._0("ipsum").build();
+    assert_eq!(wrapper, Wrapper("ipsum"));
+}
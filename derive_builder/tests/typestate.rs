@@ -0,0 +1,125 @@
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::typestate::{Set, Unset};
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate)]
+struct Lorem {
+    ipsum: u32,
+    #[builder(setter(into))]
+    dolor: String,
+    #[builder(default = "7")]
+    sit: u8,
+    #[builder(setter(each(name = "item")))]
+    amet: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate, default)]
+struct Defaulted {
+    ipsum: u32,
+    dolor: String,
+}
+
+impl Default for Defaulted {
+    fn default() -> Self {
+        Defaulted {
+            ipsum: 42,
+            dolor: "dolor".into(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate)]
+struct Generic<'a, T: Clone> {
+    reference: &'a str,
+    value: T,
+    #[builder(setter(skip))]
+    skipped: Option<T>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(typestate, try_setter, setter(into))]
+struct Fallible {
+    small: u8,
+    #[builder(default)]
+    big: u64,
+}
+
+#[test]
+fn build_once_all_required_fields_are_set() {
+    let x: Lorem = LoremBuilder::default()
+        .ipsum(1)
+        .item(2)
+        .dolor("dolor")
+        .item(3)
+        .build();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: 1,
+            dolor: "dolor".into(),
+            sit: 7,
+            amet: vec![2, 3],
+        }
+    );
+}
+
+#[test]
+fn setter_order_does_not_matter() {
+    let x = LoremBuilder::default()
+        .sit(1)
+        .dolor("dolor")
+        .amet(vec![])
+        .ipsum(2)
+        .build();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: 2,
+            dolor: "dolor".into(),
+            sit: 1,
+            amet: vec![],
+        }
+    );
+}
+
+#[test]
+fn typestate_is_visible_in_type() {
+    let partial: LoremBuilder<Set, Unset, Unset> = LoremBuilder::default().ipsum(1);
+    let complete: LoremBuilder<Set, Set, Set> = partial.dolor("dolor").amet(vec![1]);
+    assert_eq!(complete.build().ipsum, 1);
+}
+
+#[test]
+fn struct_default_makes_fields_optional() {
+    assert_eq!(DefaultedBuilder::default().build(), Defaulted::default());
+    assert_eq!(DefaultedBuilder::default().ipsum(1).build().ipsum, 1);
+}
+
+#[test]
+fn generic() {
+    let x = GenericBuilder::default().value(1).reference("ref").build();
+
+    assert_eq!(
+        x,
+        Generic {
+            reference: "ref",
+            value: 1,
+            skipped: None,
+        }
+    );
+}
+
+#[test]
+fn try_setter() {
+    assert!(FallibleBuilder::default().try_small(300u16).is_err());
+
+    let x = FallibleBuilder::default().try_small(3u16).unwrap().build();
+
+    assert_eq!(x, Fallible { small: 3, big: 0 });
+}
@@ -0,0 +1,176 @@
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::ValidationErrors;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate", validate_target = "Range::validate"))]
+struct Range {
+    #[builder(default = "0")]
+    start: u32,
+    #[builder(default = "10")]
+    end: u32,
+}
+
+impl RangeBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.start {
+            Some(100) => Err("start must not be 100".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Range {
+    fn validate(&self) -> Result<(), String> {
+        if self.start > self.end {
+            Err(format!("start {} is after end {}", self.start, self.end))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate_target = "Custom::validate", error = "MyError"))]
+struct Custom {
+    #[builder(default = "vec![1]")]
+    values: Vec<u8>,
+    limit: usize,
+}
+
+impl Custom {
+    fn validate(&self) -> Result<(), MyError> {
+        if self.values.len() > self.limit {
+            Err(MyError::TooMany(self.values.len()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum MyError {
+    Uninitialized(&'static str),
+    TooMany(usize),
+}
+
+impl From<derive_builder::UninitializedFieldError> for MyError {
+    fn from(e: derive_builder::UninitializedFieldError) -> Self {
+        Self::Uninitialized(e.field_name())
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(collect_validation_errors, validate_target = "Window::validate"))]
+struct Window {
+    #[builder(default = "640", validate(range(max = 1920)))]
+    width: u32,
+    #[builder(default = "480")]
+    height: u32,
+}
+
+impl Window {
+    fn validate(&self, errors: &mut ValidationErrors) {
+        if self.width < self.height {
+            errors.push("height", "must not be greater than the width");
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Connection {
+    host: String,
+    port: u16,
+}
+
+#[derive_builder::builder(build_fn(validate_target = "check_connection"))]
+fn connect(
+    #[builder(default = "\"localhost\".to_string()")] host: String,
+    port: u16,
+) -> Connection {
+    Connection { host, port }
+}
+
+fn check_connection(connection: &Connection) -> Result<(), String> {
+    if connection.host == "localhost" && connection.port < 1024 {
+        Err("privileged port on localhost".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[test]
+fn valid() {
+    let range = RangeBuilder::default().start(5).build().unwrap();
+    assert_eq!(range, Range { start: 5, end: 10 });
+}
+
+#[test]
+fn defaults_are_validated() {
+    let err = RangeBuilder::default().start(20).build().unwrap_err();
+    match err {
+        RangeBuilderError::ValidationError(ref message) => {
+            assert_eq!(message, "start 20 is after end 10");
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn builder_validated_first() {
+    let err = RangeBuilder::default().start(100).build().unwrap_err();
+    assert_eq!(&err.to_string(), "start must not be 100");
+}
+
+#[test]
+fn custom_error() {
+    let err = CustomBuilder::default().limit(0).build().unwrap_err();
+    assert_eq!(err, MyError::TooMany(1));
+
+    let err = CustomBuilder::default().build().unwrap_err();
+    assert_eq!(err, MyError::Uninitialized("limit"));
+
+    let custom = CustomBuilder::default().limit(1).build().unwrap();
+    assert_eq!(
+        custom,
+        Custom {
+            values: vec![1],
+            limit: 1,
+        }
+    );
+}
+
+#[test]
+fn collect_validation_errors() {
+    let err = WindowBuilder::default()
+        .width(2000)
+        .height(4000)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "`width`: must be at most 1920; `height`: must not be greater than the width"
+    );
+
+    let err = WindowBuilder::default().height(800).build().unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "`height`: must not be greater than the width"
+    );
+}
+
+#[test]
+fn function() {
+    let err = ConnectBuilder::default().port(80).build().unwrap_err();
+    assert_eq!(&err.to_string(), "privileged port on localhost");
+
+    let connection = ConnectBuilder::default().port(8080).build().unwrap();
+    assert_eq!(
+        connection,
+        Connection {
+            host: "localhost".to_string(),
+            port: 8080,
+        }
+    );
+}
@@ -0,0 +1,164 @@
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::ValidationErrors;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    setter(into),
+    build_fn(collect_validation_errors, validate = "Self::validate")
+)]
+struct Service {
+    #[builder(validate(non_empty, len(max = 8)))]
+    name: String,
+    #[builder(default = "0", validate(range(min = 1, max = 65535)))]
+    port: u32,
+    #[builder(default = "1")]
+    workers: u32,
+}
+
+impl ServiceBuilder {
+    fn validate(&self, errors: &mut ValidationErrors) {
+        if self.workers == Some(0) {
+            errors.push("workers", "must not be zero");
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(collect_validation_errors, error = "MyError"))]
+struct Custom {
+    #[builder(validate(range(min = 1)))]
+    ipsum: u32,
+    #[builder(validate(range(max = 10)))]
+    dolor: u32,
+}
+
+#[derive(Debug, PartialEq)]
+enum MyError {
+    Uninitialized(&'static str),
+    Invalid(Vec<(String, String)>),
+}
+
+impl From<derive_builder::UninitializedFieldError> for MyError {
+    fn from(e: derive_builder::UninitializedFieldError) -> Self {
+        Self::Uninitialized(e.field_name())
+    }
+}
+
+impl From<ValidationErrors> for MyError {
+    fn from(e: ValidationErrors) -> Self {
+        Self::Invalid(
+            e.iter()
+                .map(|(path, message)| (path.to_string(), message.to_string()))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Connection {
+    host: String,
+    port: u16,
+}
+
+#[derive_builder::builder(setter(into), build_fn(collect_validation_errors))]
+fn connect(
+    #[builder(validate(non_empty))] host: String,
+    #[builder(default = "0", validate(range(min = 1)))] port: u16,
+) -> Connection {
+    Connection { host, port }
+}
+
+#[test]
+fn valid() {
+    let service = ServiceBuilder::default()
+        .name("api")
+        .port(443u32)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        service,
+        Service {
+            name: "api".to_string(),
+            port: 443,
+            workers: 1,
+        }
+    );
+}
+
+#[test]
+fn all_errors() {
+    let err = ServiceBuilder::default()
+        .name("")
+        .workers(0u32)
+        .build()
+        .unwrap_err();
+
+    match err {
+        ServiceBuilderError::ValidationErrors(ref errors) => {
+            assert_eq!(errors.len(), 3);
+            assert_eq!(
+                errors.iter().collect::<Vec<_>>(),
+                vec![
+                    ("workers", "must not be zero"),
+                    ("name", "must not be empty"),
+                    ("port", "must be at least 1"),
+                ]
+            );
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+    assert_eq!(
+        &err.to_string(),
+        "`workers`: must not be zero; `name`: must not be empty; `port`: must be at least 1"
+    );
+}
+
+#[test]
+fn uninitialized() {
+    let err = ServiceBuilder::default().port(0u32).build().unwrap_err();
+    assert_eq!(&err.to_string(), "`name` must be initialized");
+}
+
+#[test]
+fn custom_error() {
+    let err = CustomBuilder::default()
+        .ipsum(0)
+        .dolor(11)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        MyError::Invalid(vec![
+            ("ipsum".to_string(), "must be at least 1".to_string()),
+            ("dolor".to_string(), "must be at most 10".to_string()),
+        ])
+    );
+
+    let err = CustomBuilder::default().ipsum(0).build().unwrap_err();
+    assert_eq!(err, MyError::Uninitialized("dolor"));
+}
+
+#[test]
+fn function() {
+    let err = ConnectBuilder::default().host("").build().unwrap_err();
+    assert_eq!(
+        &err.to_string(),
+        "`host`: must not be empty; `port`: must be at least 1"
+    );
+
+    let connection = ConnectBuilder::default()
+        .host("localhost")
+        .port(80u16)
+        .build()
+        .unwrap();
+    assert_eq!(
+        connection,
+        Connection {
+            host: "localhost".to_string(),
+            port: 80,
+        }
+    );
+}
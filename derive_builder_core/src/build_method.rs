@@ -4,7 +4,11 @@ use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt};
 use syn::spanned::Spanned;
 
-use crate::{BuilderPattern, DefaultExpression, DEFAULT_STRUCT_NAME};
+use crate::field_validation::VALIDATION_ERRORS_NAME;
+use crate::{
+    BlockContents, BuilderPattern, DefaultExpression, GroupCheck, TargetFn, TrySetterErrorsField,
+    DEFAULT_STRUCT_NAME,
+};
 
 /// Initializer for the struct fields in the build method, implementing
 /// `quote::ToTokens`.
@@ -49,12 +53,36 @@ pub struct BuildMethod<'a> {
     ///
     /// The corresonding builder field will be `Option<field_type>`.
     pub target_ty: &'a syn::Ident,
+    /// Variant of the target type to construct, if the target type is an enum.
+    pub target_variant: Option<&'a syn::Ident>,
+    /// Function to call with the initializers as arguments, instead of constructing the
+    /// target type, if the builder is generated for the parameters of a function.
+    pub target_fn: Option<&'a TargetFn>,
     /// Type parameters and lifetimes attached to this builder struct.
     pub target_ty_generics: Option<syn::TypeGenerics<'a>>,
     /// Type of error.
     pub error_ty: syn::Path,
+    /// Whether the build method returns the target type directly instead of a `Result`.
+    pub infallible: bool,
     /// Field initializers for the target type.
+    ///
+    /// If there are computed fields, the initializers are emitted without the field names, and
+    /// their values are bound to the variables named in `bound_fields`.
     pub initializers: Vec<TokenStream>,
+    /// Fields of the target type, and the variables their initializers are bound to if there
+    /// are computed fields.
+    pub bound_fields: Vec<(syn::Member, &'a syn::Ident)>,
+    /// Fields of the target type which are computed from the values of the other fields,
+    /// once all of them are initialized, in the order of their declaration.
+    pub computed_fields: Vec<ComputedField<'a>>,
+    /// Required fields which are checked together before any initializer runs, so that
+    /// every missing field is reported in one `UninitializedFieldsError`.
+    ///
+    /// This is empty unless `build_fn(collect_uninitialized)` is used.
+    pub collected_fields: Vec<&'a syn::Ident>,
+    /// Checks of the groups of fields declared with `#[builder(group(...))]`, which run
+    /// before any other validation.
+    pub group_checks: Vec<GroupCheck<'a>>,
     /// Doc-comment of the builder struct.
     pub doc_comment: Option<syn::Attribute>,
     /// Default value for the whole struct.
@@ -63,16 +91,106 @@ pub struct BuildMethod<'a> {
     pub default_struct: Option<&'a DefaultExpression>,
     /// Validation function with signature `&FooBuilder -> Result<(), String>`
     /// to call before the macro-provided struct buildout.
+    ///
+    /// When validation errors are collected, the signature is
+    /// `(&FooBuilder, &mut ValidationErrors)` instead.
     pub validate_fn: Option<&'a syn::Path>,
+    /// Validation function with signature `&Foo -> Result<(), String>` to call after the
+    /// target has been constructed.
+    ///
+    /// When validation errors are collected, the signature is
+    /// `(&Foo, &mut ValidationErrors)` instead.
+    pub validate_target_fn: Option<&'a syn::Path>,
+    /// Whether all validation errors are collected into a `ValidationErrors`, which is
+    /// returned after all initializers have run, instead of returning the first one.
+    pub collect_validation_errors: bool,
+    /// Whether fallible setters record their errors in the builder, which are returned
+    /// before any other check runs.
+    pub try_setter_errors: bool,
 }
 
 impl<'a> ToTokens for BuildMethod<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_root = self.crate_root;
         let ident = &self.ident;
         let vis = &self.visibility;
         let target_ty = &self.target_ty;
         let target_ty_generics = &self.target_ty_generics;
         let initializers = &self.initializers;
+        let asyncness = self
+            .target_fn
+            .filter(|target_fn| target_fn.is_async)
+            .map(|_| quote!(async));
+        let validation_errors = syn::Ident::new(VALIDATION_ERRORS_NAME, Span::call_site());
+        let return_validation_errors = quote!(
+            if !#validation_errors.is_empty() {
+                return #crate_root::export::core::result::Result::Err(
+                    #crate_root::export::core::convert::Into::into(#validation_errors)
+                );
+            }
+        );
+        let (output_ty, mut construct) = match self.target_fn {
+            // The function must not be called before all validation errors have been checked,
+            // so its arguments are evaluated first.
+            Some(target_fn) if self.collect_validation_errors => {
+                let path = &target_fn.path;
+                let await_call = asyncness.as_ref().map(|_| quote!(.await));
+                let indices = (0..initializers.len()).map(syn::Index::from);
+                (
+                    target_fn.output.to_token_stream(),
+                    quote!({
+                        let arguments = (#(#initializers)*);
+                        #return_validation_errors
+                        #path(#(arguments.#indices),*) #await_call
+                    }),
+                )
+            }
+            Some(target_fn) => {
+                let path = &target_fn.path;
+                let await_call = asyncness.as_ref().map(|_| quote!(.await));
+                (
+                    target_fn.output.to_token_stream(),
+                    quote!(#path(#(#initializers)*) #await_call),
+                )
+            }
+            None => {
+                let target_constructor = match self.target_variant {
+                    Some(variant) => quote!(#target_ty::#variant),
+                    None => quote!(#target_ty),
+                };
+                let mut construct = if self.computed_fields.is_empty() {
+                    quote!(#target_constructor {
+                        #(#initializers)*
+                    })
+                } else {
+                    self.construct_with_computed_fields(target_constructor)
+                };
+                if self.collect_validation_errors && self.validate_target_fn.is_none() {
+                    construct = quote!({
+                        let target = #construct;
+                        #return_validation_errors
+                        target
+                    });
+                }
+                (quote!(#target_ty #target_ty_generics), construct)
+            }
+        };
+        if let Some(vfn) = self.validate_target_fn {
+            let validate_target = if self.collect_validation_errors {
+                quote_spanned!(vfn.span() => #vfn(&target, &mut #validation_errors);)
+            } else {
+                quote_spanned!(vfn.span() => #vfn(&target)?;)
+            };
+            let return_validation_errors = self
+                .collect_validation_errors
+                .then(|| return_validation_errors.clone());
+            construct = quote!({
+                let target = #construct;
+                #validate_target
+                #return_validation_errors
+                target
+            });
+        }
         let self_param = match self.pattern {
             BuilderPattern::Owned => quote!(self),
             BuilderPattern::Mutable | BuilderPattern::Immutable => quote!(&self),
@@ -83,27 +201,160 @@ impl<'a> ToTokens for BuildMethod<'a> {
             let ident = syn::Ident::new(DEFAULT_STRUCT_NAME, Span::call_site());
             quote!(let #ident: #target_ty #target_ty_generics = #default_expr;)
         });
-        let validate_fn = self
-            .validate_fn
-            .as_ref()
-            .map(|vfn| quote_spanned!(vfn.span() => #vfn(&self)?;));
+        let try_setter_errors = TrySetterErrorsField::ident();
+        let group_checks = &self.group_checks;
+        let validate_fn = if self.collect_validation_errors {
+            let validate_fn = self
+                .validate_fn
+                .as_ref()
+                .map(|vfn| quote_spanned!(vfn.span() => #vfn(&self, &mut #validation_errors);));
+            let push_try_setter_errors = self.try_setter_errors.then(|| {
+                quote!(
+                    for error in &self.#try_setter_errors {
+                        #validation_errors.push(error.field_name(), error.message());
+                    }
+                )
+            });
+            Some(quote!(
+                #[allow(unused_mut)]
+                let mut #validation_errors = #crate_root::ValidationErrors::new();
+                #push_try_setter_errors
+                #(#group_checks)*
+                #validate_fn
+            ))
+        } else {
+            self.validate_fn
+                .as_ref()
+                .map(|vfn| quote_spanned!(vfn.span() => #vfn(&self)?;))
+        };
+        let return_try_setter_error = (self.try_setter_errors
+            && !self.collect_validation_errors)
+            .then(|| {
+                quote!(
+                    if let #crate_root::export::core::option::Option::Some(error) = self.#try_setter_errors.first() {
+                        return #crate_root::export::core::result::Result::Err(
+                            #crate_root::export::core::convert::Into::into(
+                                #crate_root::export::core::clone::Clone::clone(error)
+                            )
+                        );
+                    }
+                )
+            });
+        // With `collect_validation_errors`, the group checks add their errors to the
+        // `ValidationErrors` of `validate_fn` instead
+        let group_checks: &[GroupCheck] = if self.collect_validation_errors {
+            &[]
+        } else {
+            group_checks
+        };
         let error_ty = &self.error_ty;
+        let collect_uninitialized = if self.collected_fields.is_empty() {
+            None
+        } else {
+            let checks = self.collected_fields.iter().map(|field| {
+                let field_name = field.to_string();
+                quote!(
+                    if self.#field.is_none() {
+                        uninitialized.push(#field_name);
+                    }
+                )
+            });
+            Some(quote!({
+                let mut uninitialized = #crate_root::UninitializedFieldsError::new();
+                #(#checks)*
+                if !uninitialized.is_empty() {
+                    return #crate_root::export::core::result::Result::Err(
+                        #crate_root::export::core::convert::Into::into(uninitialized)
+                    );
+                }
+            }))
+        };
 
         if self.enabled {
-            let crate_root = &self.crate_root;
-            tokens.append_all(quote!(
-                #doc_comment
-                #vis fn #ident(#self_param)
-                    -> #crate_root::export::core::result::Result<#target_ty #target_ty_generics, #error_ty>
-                {
-                    #validate_fn
-                    #default_struct
-                    Ok(#target_ty {
-                        #(#initializers)*
-                    })
-                }
-            ))
+            if self.infallible {
+                tokens.append_all(quote!(
+                    #doc_comment
+                    #vis #asyncness fn #ident(#self_param) -> #output_ty {
+                        #default_struct
+                        #construct
+                    }
+                ))
+            } else {
+                tokens.append_all(quote!(
+                    #doc_comment
+                    #vis #asyncness fn #ident(#self_param)
+                        -> #crate_root::export::core::result::Result<#output_ty, #error_ty>
+                    {
+                        #return_try_setter_error
+                        #(#group_checks)*
+                        #validate_fn
+                        #default_struct
+                        #collect_uninitialized
+                        Ok(#construct)
+                    }
+                ))
+            }
+        }
+    }
+}
+
+/// Field of the target type which the build method computes from the final values of the
+/// other fields, from `#[builder(computed = "...")]`.
+#[derive(Debug)]
+pub struct ComputedField<'a> {
+    /// The target field, by name or by index.
+    pub member: syn::Member,
+    /// Name of the variable holding the value, which is also the name of the builder field.
+    pub ident: &'a syn::Ident,
+    /// Type of the target field.
+    pub ty: &'a syn::Type,
+    /// Expression computing the value, in which the fields which aren't computed and the
+    /// previous computed fields are references.
+    pub expr: &'a BlockContents,
+}
+
+impl<'a> BuildMethod<'a> {
+    /// Bind the initialized fields to variables, then compute the computed fields in order,
+    /// each seeing references to the initialized fields and the computed fields before it, and
+    /// construct the target from the variables.
+    fn construct_with_computed_fields(&self, target_constructor: TokenStream) -> TokenStream {
+        let initializers = &self.initializers;
+        let bound: Vec<&syn::Ident> = self.bound_fields.iter().map(|(_, ident)| *ident).collect();
+
+        let mut available = bound.clone();
+        let mut computed = TokenStream::new();
+        for field in &self.computed_fields {
+            let ident = field.ident;
+            let ty = field.ty;
+            let expr = field.expr;
+            let references = available.iter().map(|ident| {
+                quote!(
+                    #[allow(unused_variables)]
+                    let #ident = &#ident;
+                )
+            });
+            computed.append_all(quote!(
+                let #ident: #ty = {
+                    #(#references)*
+                    #expr
+                };
+            ));
+            available.push(ident);
         }
+
+        let members = self
+            .bound_fields
+            .iter()
+            .map(|(member, _)| member)
+            .chain(self.computed_fields.iter().map(|field| &field.member));
+
+        quote!({
+            let (#(#bound,)*) = (#(#initializers)*);
+            #computed
+            #target_constructor {
+                #(#members: #available,)*
+            }
+        })
     }
 }
 
@@ -127,12 +378,22 @@ macro_rules! default_build_method {
             visibility: ::std::borrow::Cow::Owned(syn::parse_quote!(pub)),
             pattern: BuilderPattern::Mutable,
             target_ty: &syn::Ident::new("Foo", ::proc_macro2::Span::call_site()),
+            target_variant: None,
+            target_fn: None,
             target_ty_generics: None,
             error_ty: syn::parse_quote!(FooBuilderError),
+            infallible: false,
             initializers: vec![quote!(foo: self.foo,)],
+            bound_fields: vec![],
+            computed_fields: vec![],
+            collected_fields: vec![],
+            group_checks: vec![],
             doc_comment: None,
             default_struct: None,
             validate_fn: None,
+            validate_target_fn: None,
+            collect_validation_errors: false,
+            try_setter_errors: false,
         }
     };
 }
@@ -211,6 +472,130 @@ mod tests {
         );
     }
 
+    #[test]
+    fn enum_variant() {
+        let variant = syn::Ident::new("Bar", Span::call_site());
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.target_variant = Some(&variant);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    Ok(Foo::Bar {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn target_fn() {
+        let target_fn = TargetFn {
+            path: quote!(<Client>::new),
+            output: parse_quote!(Client),
+            name: "Client::new".into(),
+            is_async: false,
+            receiver_lifetime: None,
+        };
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.target_fn = Some(&target_fn);
+        build_method.initializers = vec![quote!(self.foo,), quote!(self.bar,)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Client, FooBuilderError> {
+                    Ok(<Client>::new(self.foo, self.bar,))
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn async_target_fn() {
+        let target_fn = TargetFn {
+            path: quote!(<Client>::__fetch),
+            output: parse_quote!(String),
+            name: "Client::fetch".into(),
+            is_async: true,
+            receiver_lifetime: Some(parse_quote!('__receiver)),
+        };
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.target_fn = Some(&target_fn);
+        build_method.initializers = vec![quote!(self.foo,)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub async fn build(&self) -> ::db::export::core::result::Result<String, FooBuilderError> {
+                    Ok(<Client>::__fetch(self.foo,).await)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn infallible() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.infallible = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> Foo {
+                    Foo {
+                        foo: self.foo,
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn collect_uninitialized() {
+        let foo = syn::Ident::new("foo", Span::call_site());
+        let bar = syn::Ident::new("bar", Span::call_site());
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.collected_fields = vec![&foo, &bar];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    {
+                        let mut uninitialized = ::db::UninitializedFieldsError::new();
+                        if self.foo.is_none() {
+                            uninitialized.push("foo");
+                        }
+                        if self.bar.is_none() {
+                            uninitialized.push("bar");
+                        }
+                        if !uninitialized.is_empty() {
+                            return ::db::export::core::result::Result::Err(
+                                ::db::export::core::convert::Into::into(uninitialized)
+                            );
+                        }
+                    }
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn validation() {
         let validate_path: syn::Path = parse_quote!(IpsumBuilder::validate);
@@ -233,4 +618,201 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn validate_target() {
+        let validate_path: syn::Path = parse_quote!(Foo::validate);
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.validate_target_fn = Some(&validate_path);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    Ok({
+                        let target = Foo {
+                            foo: self.foo,
+                        };
+                        Foo::validate(&target)?;
+                        target
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn validate_target_collect_validation_errors() {
+        let validate_path: syn::Path = parse_quote!(Foo::validate);
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.validate_target_fn = Some(&validate_path);
+        build_method.collect_validation_errors = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    #[allow(unused_mut)]
+                    let mut __validation_errors = ::db::ValidationErrors::new();
+
+                    Ok({
+                        let target = Foo {
+                            foo: self.foo,
+                        };
+                        Foo::validate(&target, &mut __validation_errors);
+                        if !__validation_errors.is_empty() {
+                            return ::db::export::core::result::Result::Err(
+                                ::db::export::core::convert::Into::into(__validation_errors)
+                            );
+                        }
+                        target
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn collect_validation_errors() {
+        let validate_path: syn::Path = parse_quote!(IpsumBuilder::validate);
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.validate_fn = Some(&validate_path);
+        build_method.collect_validation_errors = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    #[allow(unused_mut)]
+                    let mut __validation_errors = ::db::ValidationErrors::new();
+                    IpsumBuilder::validate(&self, &mut __validation_errors);
+
+                    Ok({
+                        let target = Foo {
+                            foo: self.foo,
+                        };
+                        if !__validation_errors.is_empty() {
+                            return ::db::export::core::result::Result::Err(
+                                ::db::export::core::convert::Into::into(__validation_errors)
+                            );
+                        }
+                        target
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn collect_validation_errors_target_fn() {
+        let target_fn = TargetFn {
+            path: quote!(connect),
+            output: parse_quote!(Connection),
+            name: "connect".to_string(),
+            is_async: false,
+            receiver_lifetime: None,
+        };
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.target_fn = Some(&target_fn);
+        build_method.initializers = vec![quote!(self.host,), quote!(self.port,)];
+        build_method.collect_validation_errors = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Connection, FooBuilderError> {
+                    #[allow(unused_mut)]
+                    let mut __validation_errors = ::db::ValidationErrors::new();
+
+                    Ok({
+                        let arguments = (self.host, self.port,);
+                        if !__validation_errors.is_empty() {
+                            return ::db::export::core::result::Result::Err(
+                                ::db::export::core::convert::Into::into(__validation_errors)
+                            );
+                        }
+                        connect(arguments.0, arguments.1)
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn try_setter_errors() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.try_setter_errors = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    if let ::db::export::core::option::Option::Some(error) = self.__try_setter_errors.first() {
+                        return ::db::export::core::result::Result::Err(
+                            ::db::export::core::convert::Into::into(
+                                ::db::export::core::clone::Clone::clone(error)
+                            )
+                        );
+                    }
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn computed_fields() {
+        let foo = syn::Ident::new("foo", Span::call_site());
+        let bar = syn::Ident::new("bar", Span::call_site());
+        let ty = syn::parse_quote!(usize);
+        let expr: syn::Expr = syn::parse_quote!(foo.len());
+        let expr = BlockContents::from(expr);
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.initializers = vec![quote!(self.foo,)];
+        build_method.bound_fields = vec![(syn::Member::Named(foo.clone()), &foo)];
+        build_method.computed_fields = vec![ComputedField {
+            member: syn::Member::Named(bar.clone()),
+            ident: &bar,
+            ty: &ty,
+            expr: &expr,
+        }];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    Ok({
+                        let (foo,) = (self.foo,);
+                        let bar: usize = {
+                            #[allow(unused_variables)]
+                            let foo = &foo;
+                            { foo.len() }
+                        };
+                        Foo {
+                            foo: foo,
+                            bar: bar,
+                        }
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
 }
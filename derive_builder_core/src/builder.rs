@@ -5,7 +5,7 @@ use quote::{format_ident, ToTokens, TokenStreamExt};
 use syn::punctuated::Punctuated;
 use syn::{Path, TraitBound, TraitBoundModifier, TypeParamBound};
 
-use crate::BuilderPattern;
+use crate::{BuilderPattern, IntoFuture, Typestate};
 
 const ALLOC_NOT_ENABLED_ERROR: &str = r#"`alloc` is disabled within 'derive_builder', consider one of the following:
 * enable feature `alloc` on 'derive_builder' if a `global_allocator` is present
@@ -135,6 +135,41 @@ pub struct Builder<'a> {
     /// This would be `false` when `build_fn.error.as_validation_error() == Some((false, _))`. This
     /// has no effect when `generate_error` is `false`.
     pub generate_validation_error: bool,
+    /// Whether to include `UninitializedFields` in the generated enum, for build methods which
+    /// report all missing fields at once.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_uninitialized_fields_error: bool,
+    /// Whether to include `SubfieldBuild` in the generated enum, for builders with
+    /// `sub_builder` fields.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_subfield_error: bool,
+    /// Whether to include `FieldValidation` in the generated enum, for builders with
+    /// `validate` fields.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_field_validation_error: bool,
+    /// Whether to include `ValidationErrors` in the generated enum, for builders which
+    /// collect all validation errors.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_validation_errors_error: bool,
+    /// Whether to include `GroupViolation` in the generated enum, for builders with groups
+    /// of fields.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_group_violation_error: bool,
+    /// Whether to include `TrySetter` in the generated enum, for builders whose fallible
+    /// setters record their errors.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_try_setter_error: bool,
+    /// Whether to convert the generated error into `SubfieldBuildError`, for builders which are
+    /// the `sub_builder` of another builder.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_into_subfield_error: bool,
     /// Indicator of `cfg!(not(any(feature = "alloc", feature = "std")))`, as a field for tests
     pub no_alloc: bool,
     /// Whether this builder must derive `Clone`.
@@ -146,6 +181,14 @@ pub struct Builder<'a> {
     pub doc_comment: Option<syn::Attribute>,
     /// Whether or not a libstd is used.
     pub std: bool,
+    /// Type-level tracking of required fields, for builders with `#[builder(typestate)]`.
+    ///
+    /// When set, the build method is emitted from here, in its own `impl` block, rather
+    /// than as part of `functions`.
+    pub typestate: Option<Typestate<'a>>,
+    /// Implementation of `IntoFuture` which awaits the build method, if the builder is
+    /// generated for an `async` function.
+    pub into_future: Option<IntoFuture<'a>>,
 }
 
 impl<'a> ToTokens for Builder<'a> {
@@ -155,10 +198,11 @@ impl<'a> ToTokens for Builder<'a> {
             let builder_vis = &self.visibility;
             let builder_ident = &self.ident;
             // Splitting because Generics doesn't output WhereClause, see dtolnay/syn#782
-            let (struct_generics, struct_where_clause) = (
-                self.generics,
-                self.generics.and_then(|g| g.where_clause.as_ref()),
-            );
+            let struct_generics = match self.typestate {
+                Some(ref typestate) => Some(Cow::Owned(typestate.struct_generics())),
+                None => self.generics.map(Cow::Borrowed),
+            };
+            let struct_where_clause = self.generics.and_then(|g| g.where_clause.as_ref());
             let bounded_generics = self.compute_impl_bounds();
             let (impl_generics, impl_ty_generics, impl_where_clause) =
                 bounded_generics.split_for_impl();
@@ -188,6 +232,7 @@ impl<'a> ToTokens for Builder<'a> {
             let impl_attrs = self.impl_attrs;
 
             let builder_doc_comment = &self.doc_comment;
+            let typestate_field = self.typestate.as_ref().map(Typestate::field);
 
             #[cfg(not(feature = "clippy"))]
             tokens.append_all(quote!(#[allow(clippy::all)]));
@@ -201,35 +246,98 @@ impl<'a> ToTokens for Builder<'a> {
                 #builder_doc_comment
                 #builder_vis struct #builder_ident #struct_generics #struct_where_clause {
                     #(#builder_fields)*
+                    #typestate_field
                 }
             ));
 
             #[cfg(not(feature = "clippy"))]
             tokens.append_all(quote!(#[allow(clippy::all)]));
 
-            tokens.append_all(quote!(
-                #(#impl_attrs)*
-                #[allow(dead_code)]
-                impl #impl_generics #builder_ident #impl_ty_generics #impl_where_clause {
-                    #(#functions)*
+            if let Some(ref typestate) = self.typestate {
+                let mut typestate_generics = bounded_generics.clone();
+                typestate.extend_impl_generics(&mut typestate_generics);
+                let (typestate_impl_generics, typestate_ty_generics, _) =
+                    typestate_generics.split_for_impl();
+                let unset_ty = typestate.unset_ty();
+                let set_ty = typestate.set_ty();
+                let typestate_field_initializer = typestate.field_initializer();
+                let build_fn = &typestate.build_fn;
+                let into_future = self.into_future.as_ref().map(|into_future| {
+                    into_future.impl_for(impl_generics.clone(), set_ty.clone(), impl_where_clause)
+                });
+
+                tokens.append_all(quote!(
+                    #(#impl_attrs)*
+                    #[allow(dead_code)]
+                    impl #typestate_impl_generics #builder_ident #typestate_ty_generics #impl_where_clause {
+                        #(#functions)*
+
+                        #typestate
+                    }
 
-                    /// Create an empty builder, with all fields set to `None` or `PhantomData`.
-                    fn #create_empty() -> Self {
-                        Self {
-                            #(#builder_field_initializers)*
+                    #(#impl_attrs)*
+                    #[allow(dead_code)]
+                    impl #impl_generics #unset_ty #impl_where_clause {
+                        /// Create an empty builder, with all fields set to `None` or `PhantomData`.
+                        fn #create_empty() -> Self {
+                            Self {
+                                #(#builder_field_initializers)*
+                                #typestate_field_initializer
+                            }
                         }
                     }
-                }
-            ));
 
-            if self.impl_default {
+                    #(#impl_attrs)*
+                    #[allow(dead_code)]
+                    impl #impl_generics #set_ty #impl_where_clause {
+                        #build_fn
+                    }
+
+                    #into_future
+                ));
+
+                if self.impl_default {
+                    tokens.append_all(quote!(
+                        impl #impl_generics #crate_root::export::core::default::Default for #unset_ty #impl_where_clause {
+                            fn default() -> Self {
+                                Self::#create_empty()
+                            }
+                        }
+                    ));
+                }
+            } else {
                 tokens.append_all(quote!(
-                    impl #impl_generics #crate_root::export::core::default::Default for #builder_ident #impl_ty_generics #impl_where_clause {
-                        fn default() -> Self {
-                            Self::#create_empty()
+                    #(#impl_attrs)*
+                    #[allow(dead_code)]
+                    impl #impl_generics #builder_ident #impl_ty_generics #impl_where_clause {
+                        #(#functions)*
+
+                        /// Create an empty builder, with all fields set to `None` or `PhantomData`.
+                        fn #create_empty() -> Self {
+                            Self {
+                                #(#builder_field_initializers)*
+                            }
                         }
                     }
                 ));
+
+                if let Some(ref into_future) = self.into_future {
+                    tokens.append_all(into_future.impl_for(
+                        impl_generics.clone(),
+                        quote!(#builder_ident #impl_ty_generics),
+                        impl_where_clause,
+                    ));
+                }
+
+                if self.impl_default {
+                    tokens.append_all(quote!(
+                        impl #impl_generics #crate_root::export::core::default::Default for #builder_ident #impl_ty_generics #impl_where_clause {
+                            fn default() -> Self {
+                                Self::#create_empty()
+                            }
+                        }
+                    ));
+                }
             }
 
             if self.no_alloc && self.generate_error && self.generate_validation_error {
@@ -265,6 +373,202 @@ impl<'a> ToTokens for Builder<'a> {
                 } else {
                     TokenStream::new()
                 };
+                let uninitialized_fields_error = if self.generate_uninitialized_fields_error {
+                    quote!(
+                        /// Uninitialized fields
+                        UninitializedFields(#crate_root::UninitializedFieldsError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let uninitialized_fields_from = if self.generate_uninitialized_fields_error {
+                    quote!(
+                        impl #crate_root::export::core::convert::From<#crate_root::UninitializedFieldsError> for #builder_error_ident {
+                            fn from(s: #crate_root::UninitializedFieldsError) -> Self {
+                                Self::UninitializedFields(s)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let uninitialized_fields_display = if self.generate_uninitialized_fields_error {
+                    quote!(
+                        Self::UninitializedFields(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let subfield_error = if self.generate_subfield_error {
+                    quote!(
+                        /// Error of the builder of a `sub_builder` field
+                        SubfieldBuild(#crate_root::SubfieldBuildError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let subfield_from = if self.generate_subfield_error {
+                    quote!(
+                        impl #crate_root::export::core::convert::From<#crate_root::SubfieldBuildError> for #builder_error_ident {
+                            fn from(s: #crate_root::SubfieldBuildError) -> Self {
+                                Self::SubfieldBuild(s)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let subfield_display = if self.generate_subfield_error {
+                    quote!(
+                        Self::SubfieldBuild(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let field_validation_error = if self.generate_field_validation_error {
+                    quote!(
+                        /// Failed validation of a field
+                        FieldValidation(#crate_root::FieldValidationError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let field_validation_from = if self.generate_field_validation_error {
+                    quote!(
+                        impl #crate_root::export::core::convert::From<#crate_root::FieldValidationError> for #builder_error_ident {
+                            fn from(s: #crate_root::FieldValidationError) -> Self {
+                                Self::FieldValidation(s)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let field_validation_display = if self.generate_field_validation_error {
+                    quote!(
+                        Self::FieldValidation(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let validation_errors_error = if self.generate_validation_errors_error {
+                    quote!(
+                        /// All failed validations
+                        ValidationErrors(#crate_root::ValidationErrors),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let validation_errors_from = if self.generate_validation_errors_error {
+                    quote!(
+                        impl #crate_root::export::core::convert::From<#crate_root::ValidationErrors> for #builder_error_ident {
+                            fn from(s: #crate_root::ValidationErrors) -> Self {
+                                Self::ValidationErrors(s)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let validation_errors_display = if self.generate_validation_errors_error {
+                    quote!(
+                        Self::ValidationErrors(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let group_violation_error = if self.generate_group_violation_error {
+                    quote!(
+                        /// Fields of a group set in an invalid combination
+                        GroupViolation(#crate_root::GroupViolationError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let group_violation_from = if self.generate_group_violation_error {
+                    quote!(
+                        impl #crate_root::export::core::convert::From<#crate_root::GroupViolationError> for #builder_error_ident {
+                            fn from(s: #crate_root::GroupViolationError) -> Self {
+                                Self::GroupViolation(s)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let group_violation_display = if self.generate_group_violation_error {
+                    quote!(
+                        Self::GroupViolation(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let try_setter_error = if self.generate_try_setter_error {
+                    quote!(
+                        /// Failed conversion in a fallible setter
+                        TrySetter(#crate_root::TrySetterError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let try_setter_from = if self.generate_try_setter_error {
+                    quote!(
+                        impl #crate_root::export::core::convert::From<#crate_root::TrySetterError> for #builder_error_ident {
+                            fn from(s: #crate_root::TrySetterError) -> Self {
+                                Self::TrySetter(s)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let try_setter_display = if self.generate_try_setter_error {
+                    quote!(
+                        Self::TrySetter(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                // Allow this builder to be used as the `sub_builder` of another builder.
+                let into_subfield_error = if !self.generate_into_subfield_error {
+                    TokenStream::new()
+                } else {
+                    let mut arms = vec![quote!(
+                        #builder_error_ident::UninitializedField(field) => {
+                            #crate_root::UninitializedFieldError::new(field).into()
+                        }
+                    )];
+                    if self.generate_uninitialized_fields_error {
+                        arms.push(quote!(#builder_error_ident::UninitializedFields(e) => e.into()));
+                    }
+                    if self.generate_validation_error {
+                        arms.push(quote!(#builder_error_ident::ValidationError(s) => s.into()));
+                    }
+                    if self.generate_subfield_error {
+                        arms.push(quote!(#builder_error_ident::SubfieldBuild(e) => e));
+                    }
+                    if self.generate_field_validation_error {
+                        arms.push(quote!(#builder_error_ident::FieldValidation(e) => e.into()));
+                    }
+                    if self.generate_validation_errors_error {
+                        arms.push(quote!(#builder_error_ident::ValidationErrors(e) => e.into()));
+                    }
+                    if self.generate_group_violation_error {
+                        arms.push(quote!(#builder_error_ident::GroupViolation(e) => e.into()));
+                    }
+                    if self.generate_try_setter_error {
+                        arms.push(quote!(#builder_error_ident::TrySetter(e) => e.into()));
+                    }
+                    quote!(
+                        impl #crate_root::export::core::convert::From<#builder_error_ident> for #crate_root::SubfieldBuildError {
+                            fn from(e: #builder_error_ident) -> Self {
+                                match e {
+                                    #(#arms,)*
+                                }
+                            }
+                        }
+                    )
+                };
 
                 tokens.append_all(quote!(
                     #[doc=#builder_error_doc]
@@ -273,6 +577,12 @@ impl<'a> ToTokens for Builder<'a> {
                     #builder_vis enum #builder_error_ident {
                         /// Uninitialized field
                         UninitializedField(&'static str),
+                        #uninitialized_fields_error
+                        #subfield_error
+                        #field_validation_error
+                        #validation_errors_error
+                        #group_violation_error
+                        #try_setter_error
                         #validation_error
                     }
 
@@ -282,12 +592,30 @@ impl<'a> ToTokens for Builder<'a> {
                         }
                     }
 
+                    #uninitialized_fields_from
+
+                    #subfield_from
+
+                    #field_validation_from
+
+                    #validation_errors_from
+
+                    #group_violation_from
+
+                    #try_setter_from
+
                     #validation_from
 
                     impl #crate_root::export::core::fmt::Display for #builder_error_ident {
                         fn fmt(&self, f: &mut #crate_root::export::core::fmt::Formatter) -> #crate_root::export::core::fmt::Result {
                             match self {
                                 Self::UninitializedField(ref field) => write!(f, "`{}` must be initialized", field),
+                                #uninitialized_fields_display
+                                #subfield_display
+                                #field_validation_display
+                                #validation_errors_display
+                                #group_violation_display
+                                #try_setter_display
                                 #validation_display
                             }
                         }
@@ -299,6 +627,8 @@ impl<'a> ToTokens for Builder<'a> {
                         impl std::error::Error for #builder_error_ident {}
                     ));
                 }
+
+                tokens.append_all(into_subfield_error);
             }
         }
     }
@@ -309,7 +639,7 @@ impl<'a> Builder<'a> {
     /// This enables target types to declare generics without requiring a
     /// `Clone` impl. This is the same as how the built-in derives for
     /// `Clone`, `Default`, `PartialEq`, and other traits work.
-    fn compute_impl_bounds(&self) -> syn::Generics {
+    pub(crate) fn compute_impl_bounds(&self) -> syn::Generics {
         if let Some(type_gen) = self.generics {
             let mut generics = type_gen.clone();
 
@@ -362,10 +692,19 @@ macro_rules! default_builder {
             functions: vec![quote!(fn bar() -> { unimplemented!() })],
             generate_error: true,
             generate_validation_error: true,
+            generate_uninitialized_fields_error: false,
+            generate_subfield_error: false,
+            generate_field_validation_error: false,
+            generate_validation_errors_error: false,
+            generate_group_violation_error: false,
+            generate_try_setter_error: false,
+            generate_into_subfield_error: false,
             no_alloc: false,
             must_derive_clone: true,
             doc_comment: None,
             std: true,
+            typestate: None,
+            into_future: None,
         }
     };
 }
@@ -872,6 +1211,267 @@ mod tests {
         );
     }
 
+    #[test]
+    fn uninitialized_fields_error() {
+        let mut builder = default_builder!();
+        builder.generate_uninitialized_fields_error = true;
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                add_simple_foo_builder(&mut result);
+
+                result.append_all(quote!(
+                    #[doc="Error type for FooBuilder"]
+                    #[derive(Debug)]
+                    #[non_exhaustive]
+                    pub enum FooBuilderError {
+                        /// Uninitialized field
+                        UninitializedField(&'static str),
+                        /// Uninitialized fields
+                        UninitializedFields(::db::UninitializedFieldsError),
+                        /// Custom validation error
+                        ValidationError(::db::export::core::string::String),
+                    }
+
+                    impl ::db::export::core::convert::From<::db::UninitializedFieldError> for FooBuilderError {
+                        fn from(s: ::db::UninitializedFieldError) -> Self {
+                            Self::UninitializedField(s.field_name())
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::UninitializedFieldsError> for FooBuilderError {
+                        fn from(s: ::db::UninitializedFieldsError) -> Self {
+                            Self::UninitializedFields(s)
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::export::core::string::String> for FooBuilderError {
+                        fn from(s: ::db::export::core::string::String) -> Self {
+                            Self::ValidationError(s)
+                        }
+                    }
+
+                    impl ::db::export::core::fmt::Display for FooBuilderError {
+                        fn fmt(&self, f: &mut ::db::export::core::fmt::Formatter) -> ::db::export::core::fmt::Result {
+                            match self {
+                                Self::UninitializedField(ref field) => write!(f, "`{}` must be initialized", field),
+                                Self::UninitializedFields(ref error) => write!(f, "{}", error),
+                                Self::ValidationError(ref error) => write!(f, "{}", error),
+                            }
+                        }
+                    }
+
+                    impl std::error::Error for FooBuilderError {}
+                ));
+
+                result
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn subfield_error() {
+        let mut builder = default_builder!();
+        builder.generate_subfield_error = true;
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                add_simple_foo_builder(&mut result);
+
+                result.append_all(quote!(
+                    #[doc="Error type for FooBuilder"]
+                    #[derive(Debug)]
+                    #[non_exhaustive]
+                    pub enum FooBuilderError {
+                        /// Uninitialized field
+                        UninitializedField(&'static str),
+                        /// Error of the builder of a `sub_builder` field
+                        SubfieldBuild(::db::SubfieldBuildError),
+                        /// Custom validation error
+                        ValidationError(::db::export::core::string::String),
+                    }
+
+                    impl ::db::export::core::convert::From<::db::UninitializedFieldError> for FooBuilderError {
+                        fn from(s: ::db::UninitializedFieldError) -> Self {
+                            Self::UninitializedField(s.field_name())
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::SubfieldBuildError> for FooBuilderError {
+                        fn from(s: ::db::SubfieldBuildError) -> Self {
+                            Self::SubfieldBuild(s)
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::export::core::string::String> for FooBuilderError {
+                        fn from(s: ::db::export::core::string::String) -> Self {
+                            Self::ValidationError(s)
+                        }
+                    }
+
+                    impl ::db::export::core::fmt::Display for FooBuilderError {
+                        fn fmt(&self, f: &mut ::db::export::core::fmt::Formatter) -> ::db::export::core::fmt::Result {
+                            match self {
+                                Self::UninitializedField(ref field) => write!(f, "`{}` must be initialized", field),
+                                Self::SubfieldBuild(ref error) => write!(f, "{}", error),
+                                Self::ValidationError(ref error) => write!(f, "{}", error),
+                            }
+                        }
+                    }
+
+                    impl std::error::Error for FooBuilderError {}
+                ));
+
+                result
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn into_subfield_error() {
+        let mut builder = default_builder!();
+        builder.generate_subfield_error = true;
+        builder.generate_into_subfield_error = true;
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                add_simple_foo_builder(&mut result);
+
+                result.append_all(quote!(
+                    #[doc="Error type for FooBuilder"]
+                    #[derive(Debug)]
+                    #[non_exhaustive]
+                    pub enum FooBuilderError {
+                        /// Uninitialized field
+                        UninitializedField(&'static str),
+                        /// Error of the builder of a `sub_builder` field
+                        SubfieldBuild(::db::SubfieldBuildError),
+                        /// Custom validation error
+                        ValidationError(::db::export::core::string::String),
+                    }
+
+                    impl ::db::export::core::convert::From<::db::UninitializedFieldError> for FooBuilderError {
+                        fn from(s: ::db::UninitializedFieldError) -> Self {
+                            Self::UninitializedField(s.field_name())
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::SubfieldBuildError> for FooBuilderError {
+                        fn from(s: ::db::SubfieldBuildError) -> Self {
+                            Self::SubfieldBuild(s)
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::export::core::string::String> for FooBuilderError {
+                        fn from(s: ::db::export::core::string::String) -> Self {
+                            Self::ValidationError(s)
+                        }
+                    }
+
+                    impl ::db::export::core::fmt::Display for FooBuilderError {
+                        fn fmt(&self, f: &mut ::db::export::core::fmt::Formatter) -> ::db::export::core::fmt::Result {
+                            match self {
+                                Self::UninitializedField(ref field) => write!(f, "`{}` must be initialized", field),
+                                Self::SubfieldBuild(ref error) => write!(f, "{}", error),
+                                Self::ValidationError(ref error) => write!(f, "{}", error),
+                            }
+                        }
+                    }
+
+                    impl std::error::Error for FooBuilderError {}
+
+                    impl ::db::export::core::convert::From<FooBuilderError> for ::db::SubfieldBuildError {
+                        fn from(e: FooBuilderError) -> Self {
+                            match e {
+                                FooBuilderError::UninitializedField(field) => {
+                                    ::db::UninitializedFieldError::new(field).into()
+                                },
+                                FooBuilderError::ValidationError(s) => s.into(),
+                                FooBuilderError::SubfieldBuild(e) => e,
+                            }
+                        }
+                    }
+                ));
+
+                result
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn field_validation_error() {
+        let mut builder = default_builder!();
+        builder.generate_field_validation_error = true;
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                add_simple_foo_builder(&mut result);
+
+                result.append_all(quote!(
+                    #[doc="Error type for FooBuilder"]
+                    #[derive(Debug)]
+                    #[non_exhaustive]
+                    pub enum FooBuilderError {
+                        /// Uninitialized field
+                        UninitializedField(&'static str),
+                        /// Failed validation of a field
+                        FieldValidation(::db::FieldValidationError),
+                        /// Custom validation error
+                        ValidationError(::db::export::core::string::String),
+                    }
+
+                    impl ::db::export::core::convert::From<::db::UninitializedFieldError> for FooBuilderError {
+                        fn from(s: ::db::UninitializedFieldError) -> Self {
+                            Self::UninitializedField(s.field_name())
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::FieldValidationError> for FooBuilderError {
+                        fn from(s: ::db::FieldValidationError) -> Self {
+                            Self::FieldValidation(s)
+                        }
+                    }
+
+                    impl ::db::export::core::convert::From<::db::export::core::string::String> for FooBuilderError {
+                        fn from(s: ::db::export::core::string::String) -> Self {
+                            Self::ValidationError(s)
+                        }
+                    }
+
+                    impl ::db::export::core::fmt::Display for FooBuilderError {
+                        fn fmt(&self, f: &mut ::db::export::core::fmt::Formatter) -> ::db::export::core::fmt::Result {
+                            match self {
+                                Self::UninitializedField(ref field) => write!(f, "`{}` must be initialized", field),
+                                Self::FieldValidation(ref error) => write!(f, "{}", error),
+                                Self::ValidationError(ref error) => write!(f, "{}", error),
+                            }
+                        }
+                    }
+
+                    impl std::error::Error for FooBuilderError {}
+                ));
+
+                result
+            }
+            .to_string()
+        );
+    }
+
     #[test]
     fn no_alloc_bug_using_string() {
         let mut builder = default_builder!();
@@ -0,0 +1,327 @@
+use std::borrow::Cow;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+
+use crate::typestate::TYPESTATE_TRANSITION_NAME;
+use crate::{BuilderPattern, TrySetterErrorsField};
+
+/// Method which clears a field of the builder, implementing `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{Clear, BuilderPattern};
+/// # fn main() {
+/// #     let clear = default_clear!();
+/// #
+/// #     assert_eq!(quote!(#clear).to_string(), quote!(
+/// #[doc = "Clears the value of `foo`."]
+/// #[allow(unused_mut)]
+/// pub fn clear_foo(&mut self) -> &mut Self {
+///     let mut new = self;
+///     new.foo = ::derive_builder::export::core::default::Default::default();
+///     new
+/// }
+/// #     ).to_string());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Clear<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this method.
+    pub clear_enabled: bool,
+    /// Visibility of the method, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// How the method takes and returns `self` (e.g. mutably).
+    pub pattern: BuilderPattern,
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Name of the setter of the field without its prefix, which the method is named after.
+    pub setter_ident: syn::Ident,
+    /// Whether the errors which the fallible setter of the field recorded in the builder are
+    /// discarded, with `#[builder(try_setter(defer))]`.
+    pub discard_try_setter_errors: bool,
+}
+
+impl<'a> ToTokens for Clear<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.clear_enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let vis = &self.visibility;
+        let field_ident = self.field_ident;
+        // Leading underscores are dropped, e.g. for the fields of tuple structs
+        let name = self.setter_ident.unraw().to_string();
+        let name = name.trim_start_matches('_');
+        let ident = format_ident!("clear_{}", name);
+        let doc = format!("Clears the value of `{}`.", name);
+        let (self_param, return_ty, self_into_return_ty) = pattern_tokens(crate_root, self.pattern);
+        let discard_errors = self.discard_try_setter_errors.then(|| {
+            let try_setter_errors = TrySetterErrorsField::ident();
+            let field_name = field_ident.unraw().to_string();
+            quote!(new.#try_setter_errors.retain(|error| error.field_name() != #field_name);)
+        });
+
+        tokens.append_all(quote!(
+            #[doc = #doc]
+            #[allow(unused_mut)]
+            #vis fn #ident(#self_param) -> #return_ty {
+                let mut new = #self_into_return_ty;
+                new.#field_ident = #crate_root::export::core::default::Default::default();
+                #discard_errors
+                new
+            }
+        ));
+    }
+}
+
+/// Method which clears all fields of the builder, implementing `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// /// Clears all fields of the builder.
+/// #[allow(unused_mut)]
+/// pub fn reset(&mut self) -> &mut Self {
+///     let mut new = self;
+///     new.foo = ::derive_builder::export::core::default::Default::default();
+///     new.bar = ::derive_builder::export::core::default::Default::default();
+///     new
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reset<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this method.
+    pub enabled: bool,
+    /// Visibility of the method, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// How the method takes and returns `self` (e.g. mutably).
+    pub pattern: BuilderPattern,
+    /// The builder fields which are cleared, together with the `cfg` attributes that apply
+    /// to them.
+    pub fields: Vec<(syn::Ident, Vec<&'a syn::Attribute>)>,
+    /// Builder type in which every required field is `Unset`, for typestate builders which
+    /// track required fields.
+    pub typestate: Option<TokenStream>,
+}
+
+impl<'a> ToTokens for Reset<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let vis = &self.visibility;
+        let (self_param, mut return_ty, self_into_return_ty) =
+            pattern_tokens(crate_root, self.pattern);
+        let mut return_new = quote!(new);
+        if let Some(ref typestate) = self.typestate {
+            let transition = syn::Ident::new(TYPESTATE_TRANSITION_NAME, Span::call_site());
+            return_ty = typestate.clone();
+            return_new = quote!(new.#transition());
+        }
+        let clear_fields = self.fields.iter().map(|(ident, cfg_attrs)| {
+            quote!(
+                #(#cfg_attrs)*
+                {
+                    new.#ident = #crate_root::export::core::default::Default::default();
+                }
+            )
+        });
+
+        tokens.append_all(quote!(
+            /// Clears all fields of the builder.
+            #[allow(unused_mut)]
+            #vis fn reset(#self_param) -> #return_ty {
+                let mut new = #self_into_return_ty;
+                #(#clear_fields)*
+                #return_new
+            }
+        ));
+    }
+}
+
+/// Returns how `self` is taken, the returned type and how `self` is turned into the returned
+/// value by a method which modifies the builder according to `pattern`.
+fn pattern_tokens(
+    crate_root: &syn::Path,
+    pattern: BuilderPattern,
+) -> (TokenStream, TokenStream, TokenStream) {
+    match pattern {
+        BuilderPattern::Owned => (quote!(self), quote!(Self), quote!(self)),
+        BuilderPattern::Mutable => (quote!(&mut self), quote!(&mut Self), quote!(self)),
+        BuilderPattern::Immutable => (
+            quote!(&self),
+            quote!(Self),
+            quote!(#crate_root::export::core::clone::Clone::clone(self)),
+        ),
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_clear {
+    () => {
+        Clear {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            clear_enabled: true,
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Mutable,
+            field_ident: &syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            setter_ident: syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            discard_try_setter_errors: false,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn mutable() {
+        let clear = default_clear!();
+
+        assert_eq!(
+            quote!(#clear).to_string(),
+            quote!(
+                #[doc = "Clears the value of `foo`."]
+                #[allow(unused_mut)]
+                pub fn clear_foo(&mut self) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::default::Default::default();
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn immutable() {
+        let mut clear = default_clear!();
+        clear.pattern = BuilderPattern::Immutable;
+
+        assert_eq!(
+            quote!(#clear).to_string(),
+            quote!(
+                #[doc = "Clears the value of `foo`."]
+                #[allow(unused_mut)]
+                pub fn clear_foo(&self) -> Self {
+                    let mut new = ::db::export::core::clone::Clone::clone(self);
+                    new.foo = ::db::export::core::default::Default::default();
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn renamed_setter() {
+        let mut clear = default_clear!();
+        clear.setter_ident = syn::Ident::new("with_foo", Span::call_site());
+
+        assert_eq!(
+            quote!(#clear).to_string(),
+            quote!(
+                #[doc = "Clears the value of `with_foo`."]
+                #[allow(unused_mut)]
+                pub fn clear_with_foo(&mut self) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::default::Default::default();
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn discard_try_setter_errors() {
+        let mut clear = default_clear!();
+        clear.discard_try_setter_errors = true;
+
+        assert_eq!(
+            quote!(#clear).to_string(),
+            quote!(
+                #[doc = "Clears the value of `foo`."]
+                #[allow(unused_mut)]
+                pub fn clear_foo(&mut self) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::default::Default::default();
+                    new.__try_setter_errors
+                        .retain(|error| error.field_name() != "foo");
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut clear = default_clear!();
+        clear.clear_enabled = false;
+
+        assert_eq!(quote!(#clear).to_string(), "");
+    }
+
+    #[test]
+    fn reset_typestate() {
+        let foo = syn::Ident::new("foo", Span::call_site());
+        let bar = syn::Ident::new("bar", Span::call_site());
+        let cfg: syn::Attribute = parse_quote!(#[cfg(feature = "bar")]);
+        let reset = Reset {
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            visibility: Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Owned,
+            fields: vec![(foo, vec![]), (bar, vec![&cfg])],
+            typestate: Some(quote!(FooBuilder<::db::typestate::Unset>)),
+        };
+
+        assert_eq!(
+            quote!(#reset).to_string(),
+            quote!(
+                /// Clears all fields of the builder.
+                #[allow(unused_mut)]
+                pub fn reset(self) -> FooBuilder<::db::typestate::Unset> {
+                    let mut new = self;
+                    {
+                        new.foo = ::db::export::core::default::Default::default();
+                    }
+                    #[cfg(feature = "bar")]
+                    {
+                        new.bar = ::db::export::core::default::Default::default();
+                    }
+                    new.__into_typestate()
+                }
+            )
+            .to_string()
+        );
+    }
+}
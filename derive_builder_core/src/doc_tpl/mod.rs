@@ -5,6 +5,8 @@
 //! - {struct_name}
 //! - {builder_name}
 //! - {field_name}
+//! - {variant_name}
+//! - {fn_name}
 //!
 //! Templates are used like this:
 //!
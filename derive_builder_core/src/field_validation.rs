@@ -0,0 +1,424 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::{change_span, Bounds, Each, FieldValidators};
+
+/// Name of the `ValidationErrors` in the build method of builders which collect all
+/// validation errors.
+pub(crate) const VALIDATION_ERRORS_NAME: &str = "__validation_errors";
+
+/// Checks of the final value of a field, and of the items of a collection with an `each`
+/// setter, implementing `quote::ToTokens`.
+///
+/// Lives in the initializer of the field in the body of `BuildMethod`, where the final value
+/// of the field is bound to `value`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// if value < 1 {
+///     return Err(Into::into(
+///         ::derive_builder::FieldValidationError::new("foo", "must be at least 1")
+///     ));
+/// }
+/// if let Err(message) = check_foo(&value) {
+///     return Err(Into::into(::derive_builder::FieldValidationError::new("foo", message)));
+/// }
+/// for (index, item) in (&value).into_iter().enumerate() {
+///     if let Err(message) = check_foo_item(item) {
+///         return Err(Into::into(
+///             ::derive_builder::FieldValidationError::for_item("foo", index, message)
+///         ));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FieldValidation<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the field, which is reported in the error.
+    pub field_name: String,
+    /// The validators of the field.
+    pub validators: Option<&'a FieldValidators>,
+    /// The `each` setter of the field, whose validators check the items of the collection.
+    pub each: Option<&'a Each>,
+    /// Whether the collection of the `each` setter is wrapped in an `Option`, for fields with
+    /// `setter(strip_option)`.
+    pub each_in_option: bool,
+    /// Span where the macro was told to use a preexisting error type, which must implement
+    /// `From<FieldValidationError>`.
+    pub custom_error_type_span: Option<Span>,
+    /// Whether failures are added to the `ValidationErrors` of the build method instead of
+    /// returning early, for `build_fn(collect_validation_errors)`.
+    pub collect: bool,
+}
+
+impl<'a> FieldValidation<'a> {
+    /// Returns the early return of a `FieldValidationError` with `message`, or the addition
+    /// of `message` to the collected validation errors.
+    fn fail(&self, message: TokenStream) -> TokenStream {
+        self.fail_at(None, message)
+    }
+
+    /// Like `fail`, but for the item at `index` of the collection.
+    fn fail_item(&self, message: TokenStream) -> TokenStream {
+        self.fail_at(Some(quote!(index)), message)
+    }
+
+    fn fail_at(&self, index: Option<TokenStream>, message: TokenStream) -> TokenStream {
+        let field_name = &self.field_name;
+        if self.collect {
+            let errors = syn::Ident::new(VALIDATION_ERRORS_NAME, Span::call_site());
+            return match index {
+                Some(index) => quote!(#errors.push_item(#field_name, #index, #message);),
+                None => quote!(#errors.push(#field_name, #message);),
+            };
+        }
+
+        let conv_span = self.custom_error_type_span.unwrap_or_else(Span::call_site);
+        // See `MatchNone::ReturnError` for why the span of the crate root is changed
+        let crate_root = change_span(self.crate_root.into_token_stream(), conv_span);
+        let error = match index {
+            Some(index) => {
+                quote!(#crate_root::FieldValidationError::for_item(#field_name, #index, #message))
+            }
+            None => quote!(#crate_root::FieldValidationError::new(#field_name, #message)),
+        };
+        let err_conv =
+            quote_spanned!(conv_span => #crate_root::export::core::convert::Into::into(#error));
+        quote!(return #crate_root::export::core::result::Result::Err(#err_conv);)
+    }
+
+    /// Returns the checks that `subject` is within `bounds`, describing `subject` as `what`
+    /// in the error messages.
+    fn bounds_checks(&self, subject: TokenStream, what: &str, bounds: &Bounds) -> TokenStream {
+        let mut tokens = TokenStream::new();
+        if let Some(min) = &bounds.min {
+            let message = format!("{} at least {}", what, expr_to_string(min));
+            let fail = self.fail(message.into_token_stream());
+            tokens.append_all(quote!(if #subject < #min { #fail }));
+        }
+        if let Some(max) = &bounds.max {
+            let message = format!("{} at most {}", what, expr_to_string(max));
+            let fail = self.fail(message.into_token_stream());
+            tokens.append_all(quote!(if #subject > #max { #fail }));
+        }
+        tokens
+    }
+
+    fn validators_to_tokens(&self, validators: &FieldValidators, tokens: &mut TokenStream) {
+        let crate_root = self.crate_root;
+
+        if let Some(range) = &validators.range {
+            tokens.append_all(self.bounds_checks(quote!(value), "must be", range));
+        }
+
+        if validators.non_empty.is_present() {
+            let fail = self.fail(quote!("must not be empty"));
+            tokens.append_all(quote!(if value.is_empty() { #fail }));
+        }
+
+        if let Some(len) = &validators.len {
+            tokens.append_all(self.bounds_checks(quote!(value.len()), "length must be", len));
+        }
+
+        if let Some(with) = &validators.with {
+            let fail = self.fail(quote!(message));
+            tokens.append_all(quote!(
+                if let #crate_root::export::core::result::Result::Err(message) = #with(&value) {
+                    #fail
+                }
+            ));
+        }
+    }
+
+    fn each_to_tokens(&self, each: &Each, tokens: &mut TokenStream) {
+        let crate_root = self.crate_root;
+        let items = if self.each_in_option {
+            quote!((&value).into_iter().flatten())
+        } else {
+            quote!((&value).into_iter())
+        };
+
+        let mut count_checks = TokenStream::new();
+        if each.min.is_some() || each.max.is_some() {
+            let bounds = Bounds {
+                min: each.min.clone(),
+                max: each.max.clone(),
+            };
+            count_checks = self.bounds_checks(quote!(count), "length must be", &bounds);
+        }
+
+        let mut item_checks = TokenStream::new();
+        if let Some(validate) = &each.validate {
+            let fail = self.fail_item(quote!(message));
+            item_checks.append_all(quote!(
+                if let #crate_root::export::core::result::Result::Err(message) = #validate(item) {
+                    #fail
+                }
+            ));
+        }
+        if let (true, Some(max)) = (each.unique.is_present(), &each.max) {
+            // Quadratic, but only requires `PartialEq` of the items. `max` is required, and
+            // too many items have already been reported, so the cost is bounded.
+            let fail = self.fail_item(quote!("must be unique"));
+            item_checks.append_all(quote!(
+                if count <= #max && #items.take(index).any(|other| other == item) {
+                    #fail
+                }
+            ));
+        }
+        let item_checks = if item_checks.is_empty() {
+            None
+        } else {
+            Some(quote!(
+                for (index, item) in #items.enumerate() {
+                    #item_checks
+                }
+            ))
+        };
+        if count_checks.is_empty() {
+            tokens.append_all(item_checks);
+        } else {
+            tokens.append_all(quote!({
+                let count = #items.count();
+                #count_checks
+                #item_checks
+            }));
+        }
+    }
+}
+
+impl<'a> ToTokens for FieldValidation<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if let Some(validators) = self.validators {
+            self.validators_to_tokens(validators, tokens);
+        }
+        if let Some(each) = self.each {
+            self.each_to_tokens(each, tokens);
+        }
+    }
+}
+
+/// Returns the source of a bound for error messages, e.g. `-1` rather than `- 1`.
+fn expr_to_string(expr: &syn::Expr) -> String {
+    match expr {
+        syn::Expr::Lit(lit) => lit.to_token_stream().to_string(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => format!("-{}", expr_to_string(expr)),
+        _ => expr.to_token_stream().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn all_validators() {
+        let validators = FieldValidators {
+            range: Some(Bounds {
+                min: Some(parse_quote!(-1)),
+                max: Some(parse_quote!(MAX)),
+            }),
+            non_empty: darling::util::Flag::present(),
+            len: Some(Bounds {
+                min: None,
+                max: Some(parse_quote!(64)),
+            }),
+            with: Some(parse_quote!(check_foo)),
+        };
+        let validation = FieldValidation {
+            crate_root: &parse_quote!(::db),
+            field_name: "foo".to_string(),
+            validators: Some(&validators),
+            each: None,
+            each_in_option: false,
+            custom_error_type_span: None,
+            collect: false,
+        };
+
+        assert_eq!(
+            quote!(#validation).to_string(),
+            quote!(
+                if value < -1 {
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::FieldValidationError::new("foo", "must be at least -1")
+                        )
+                    );
+                }
+                if value > MAX {
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::FieldValidationError::new("foo", "must be at most MAX")
+                        )
+                    );
+                }
+                if value.is_empty() {
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::FieldValidationError::new("foo", "must not be empty")
+                        )
+                    );
+                }
+                if value.len() > 64 {
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::FieldValidationError::new("foo", "length must be at most 64")
+                        )
+                    );
+                }
+                if let ::db::export::core::result::Result::Err(message) = check_foo(&value) {
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::FieldValidationError::new("foo", message)
+                        )
+                    );
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn collect() {
+        let validators = FieldValidators {
+            len: Some(Bounds {
+                min: Some(parse_quote!(1)),
+                max: None,
+            }),
+            with: Some(parse_quote!(check_foo)),
+            ..Default::default()
+        };
+        let validation = FieldValidation {
+            crate_root: &parse_quote!(::db),
+            field_name: "foo".to_string(),
+            validators: Some(&validators),
+            each: None,
+            each_in_option: false,
+            custom_error_type_span: None,
+            collect: true,
+        };
+
+        assert_eq!(
+            quote!(#validation).to_string(),
+            quote!(
+                if value.len() < 1 {
+                    __validation_errors.push("foo", "length must be at least 1");
+                }
+                if let ::db::export::core::result::Result::Err(message) = check_foo(&value) {
+                    __validation_errors.push("foo", message);
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn each() {
+        let each = Each {
+            min: Some(parse_quote!(1)),
+            max: Some(parse_quote!(16)),
+            validate: Some(parse_quote!(check_item)),
+            unique: darling::util::Flag::present(),
+            ..Each::from(syn::Ident::new("foo_item", Span::call_site()))
+        };
+        let validation = FieldValidation {
+            crate_root: &parse_quote!(::db),
+            field_name: "foo".to_string(),
+            validators: None,
+            each: Some(&each),
+            each_in_option: false,
+            custom_error_type_span: None,
+            collect: false,
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#validation).to_string(),
+            quote!(
+                {
+                    let count = (&value).into_iter().count();
+                    if count < 1 {
+                        return ::db::export::core::result::Result::Err(
+                            ::db::export::core::convert::Into::into(
+                                ::db::FieldValidationError::new("foo", "length must be at least 1")
+                            )
+                        );
+                    }
+                    if count > 16 {
+                        return ::db::export::core::result::Result::Err(
+                            ::db::export::core::convert::Into::into(
+                                ::db::FieldValidationError::new("foo", "length must be at most 16")
+                            )
+                        );
+                    }
+                    for (index, item) in (&value).into_iter().enumerate() {
+                        if let ::db::export::core::result::Result::Err(message) = check_item(item) {
+                            return ::db::export::core::result::Result::Err(
+                                ::db::export::core::convert::Into::into(
+                                    ::db::FieldValidationError::for_item("foo", index, message)
+                                )
+                            );
+                        }
+                        if count <= 16 && (&value).into_iter().take(index).any(|other| other == item) {
+                            return ::db::export::core::result::Result::Err(
+                                ::db::export::core::convert::Into::into(
+                                    ::db::FieldValidationError::for_item("foo", index, "must be unique")
+                                )
+                            );
+                        }
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn each_collect() {
+        let each = Each {
+            max: Some(parse_quote!(8)),
+            unique: darling::util::Flag::present(),
+            ..Each::from(syn::Ident::new("foo_item", Span::call_site()))
+        };
+        let validation = FieldValidation {
+            crate_root: &parse_quote!(::db),
+            field_name: "foo".to_string(),
+            validators: None,
+            each: Some(&each),
+            each_in_option: true,
+            custom_error_type_span: None,
+            collect: true,
+        };
+
+        assert_eq!(
+            quote!(#validation).to_string(),
+            quote!({
+                let count = (&value).into_iter().flatten().count();
+                if count > 8 {
+                    __validation_errors.push("foo", "length must be at most 8");
+                }
+                for (index, item) in (&value).into_iter().flatten().enumerate() {
+                    if count <= 8
+                        && (&value)
+                            .into_iter()
+                            .flatten()
+                            .take(index)
+                            .any(|other| other == item)
+                    {
+                        __validation_errors.push_item("foo", index, "must be unique");
+                    }
+                }
+            })
+            .to_string()
+        );
+    }
+}
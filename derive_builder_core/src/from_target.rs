@@ -0,0 +1,163 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+/// Conversion of a value of the target struct back into a builder, implementing
+/// `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::FromTarget;
+/// # fn main() {
+/// #    let from_target = default_from_target!();
+/// #
+/// #    assert_eq!(quote!(#from_target).to_string(), quote!(
+/// impl ::derive_builder::export::core::convert::From<Foo> for FooBuilder {
+///     fn from(value: Foo) -> Self {
+///         Self {
+///             foo: ::derive_builder::export::core::option::Option::Some(value.foo),
+///         }
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FromTarget<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the target struct.
+    pub target_ty: &'a syn::Ident,
+    /// Type parameters and lifetimes of the target struct.
+    pub generics: &'a syn::Generics,
+    /// The builder type, including generics and typestate parameters.
+    pub builder_ty: TokenStream,
+    /// Initializers of the builder fields from the fields of `value`, e.g.
+    /// `foo: Some(value.foo),`
+    ///
+    /// Expects each entry to be terminated by a comma.
+    pub field_initializers: Vec<TokenStream>,
+    /// Visibility of the `to_builder` method of the target struct, if it is generated.
+    pub to_builder: Option<Cow<'a, syn::Visibility>>,
+}
+
+impl<'a> ToTokens for FromTarget<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_root = self.crate_root;
+        let target_ty = self.target_ty;
+        let builder_ty = &self.builder_ty;
+        let field_initializers = &self.field_initializers;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        tokens.append_all(quote!(
+            impl #impl_generics #crate_root::export::core::convert::From<#target_ty #ty_generics> for #builder_ty #where_clause {
+                fn from(value: #target_ty #ty_generics) -> Self {
+                    Self {
+                        #(#field_initializers)*
+                    }
+                }
+            }
+        ));
+
+        if let Some(ref vis) = self.to_builder {
+            let doc = format!("Creates a builder with the values of this `{}`.", target_ty);
+            tokens.append_all(quote!(
+                impl #impl_generics #target_ty #ty_generics #where_clause {
+                    #[doc = #doc]
+                    #vis fn to_builder(&self) -> #builder_ty
+                    where
+                        Self: #crate_root::export::core::clone::Clone,
+                    {
+                        let value = #crate_root::export::core::clone::Clone::clone(self);
+                        #crate_root::export::core::convert::From::from(value)
+                    }
+                }
+            ));
+        }
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_from_target {
+    () => {
+        FromTarget {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            target_ty: &syn::Ident::new("Foo", ::proc_macro2::Span::call_site()),
+            generics: &Default::default(),
+            builder_ty: quote!(FooBuilder),
+            field_initializers: vec![quote!(foo: ::db::export::core::option::Option::Some(value.foo),)],
+            to_builder: None,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn from_impl() {
+        let from_target = default_from_target!();
+
+        assert_eq!(
+            quote!(#from_target).to_string(),
+            quote!(
+                impl ::db::export::core::convert::From<Foo> for FooBuilder {
+                    fn from(value: Foo) -> Self {
+                        Self {
+                            foo: ::db::export::core::option::Option::Some(value.foo),
+                        }
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn to_builder() {
+        let mut from_target = default_from_target!();
+        from_target.to_builder = Some(Cow::Owned(parse_quote!(pub)));
+
+        assert_eq!(
+            quote!(#from_target).to_string(),
+            quote!(
+                impl ::db::export::core::convert::From<Foo> for FooBuilder {
+                    fn from(value: Foo) -> Self {
+                        Self {
+                            foo: ::db::export::core::option::Option::Some(value.foo),
+                        }
+                    }
+                }
+
+                impl Foo {
+                    #[doc = "Creates a builder with the values of this `Foo`."]
+                    pub fn to_builder(&self) -> FooBuilder
+                    where
+                        Self: ::db::export::core::clone::Clone,
+                    {
+                        let value = ::db::export::core::clone::Clone::clone(self);
+                        ::db::export::core::convert::From::from(value)
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+}
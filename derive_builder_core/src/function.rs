@@ -0,0 +1,575 @@
+use darling::{ast::NestedMeta, Error, FromMeta};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{ext::IdentExt, spanned::Spanned};
+
+use crate::macro_options::{default_crate_root, Options};
+
+/// Attributes of a function parameter which configure its builder field rather than the
+/// parameter itself.
+const PARAM_ATTRS: &[&str] = &["builder", "builder_field_attr", "builder_setter_attr"];
+
+/// Name of the builder field which stores the receiver of a method.
+const RECEIVER_FIELD: &str = "__receiver";
+
+/// Name of the `PhantomData` field which makes the builder of a function use all generic
+/// parameters of the function and its `impl` block.
+const FN_PHANTOM_FIELD_NAME: &str = "__fn";
+
+/// A function which is called by the build method of a builder generated with the
+/// `builder` attribute macro, instead of constructing a struct.
+///
+/// The parameters of the function are the fields of the builder.
+#[derive(Debug, Clone)]
+pub struct TargetFn {
+    /// Expression which names the function, e.g. `connect` or `<Client>::new`.
+    pub path: TokenStream,
+    /// Return type of the function.
+    pub output: syn::Type,
+    /// Name of the function for documentation, e.g. `Client::new`.
+    pub name: String,
+    /// Whether the function is `async`. The build method is then `async` as well, and the
+    /// builder implements `IntoFuture`.
+    pub is_async: bool,
+    /// Lifetime of the receiver borrowed by the builder, if the function is a method.
+    pub receiver_lifetime: Option<syn::Lifetime>,
+}
+
+/// Field of the builder of a function which uses all generic parameters of the function
+/// and its `impl` block, as the parameters of the function might not, e.g. in
+/// `fn parse<T: FromStr>(s: String) -> Option<T>`.
+///
+/// This is only needed if there are generic parameters.
+#[derive(Debug)]
+pub struct FnPhantomField<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Generic parameters of the function, merged with those of its `impl` block.
+    pub generics: &'a syn::Generics,
+}
+
+impl<'a> FnPhantomField<'a> {
+    /// Name of the field.
+    pub fn ident() -> syn::Ident {
+        syn::Ident::new(FN_PHANTOM_FIELD_NAME, Span::call_site())
+    }
+
+    /// Field declaration for the builder struct.
+    ///
+    /// Type parameters are wrapped in function pointers, so they don't affect whether the
+    /// builder is `Send` or `Sync`.
+    pub fn declaration(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = Self::ident();
+        let params = self.generics.params.iter().map(|param| match param {
+            syn::GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(&#lifetime ())
+            }
+            syn::GenericParam::Type(param) => {
+                let ty = &param.ident;
+                quote!(fn() -> #ty)
+            }
+            syn::GenericParam::Const(param) => {
+                let value = &param.ident;
+                quote!([(); #value])
+            }
+        });
+        quote!(#ident: #crate_root::export::core::marker::PhantomData<(#(#params,)*)>,)
+    }
+
+    /// Field initializer for the builder struct.
+    pub fn initializer(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = Self::ident();
+        quote!(#ident: #crate_root::export::core::marker::PhantomData,)
+    }
+}
+
+/// The `impl` block of an associated function.
+struct ImplContext<'a> {
+    self_ty: &'a syn::Type,
+    self_ident: &'a syn::Ident,
+    generics: &'a syn::Generics,
+}
+
+/// Remove the builder attributes from `item`, and generate the builders they ask for.
+///
+/// A function gets a builder for its parameters, configured by `args`. In an `impl` block,
+/// every associated function marked with `#[builder]` gets a builder, configured by the
+/// arguments of that attribute.
+pub(crate) fn builders_for_item(
+    args: TokenStream,
+    item: &mut syn::Item,
+) -> darling::Result<TokenStream> {
+    match item {
+        syn::Item::Fn(item_fn) => {
+            builder_for_fn(args, &item_fn.vis, &item_fn.attrs, &mut item_fn.sig, None)
+                .map(|builder| builder.tokens)
+        }
+        syn::Item::Impl(item_impl) => {
+            let mut errors = Error::accumulator();
+
+            if !args.is_empty() {
+                errors.push(
+                    Error::custom("options of the builder of an associated function go on its `#[builder]` attribute")
+                        .with_span(&args),
+                );
+            }
+
+            if let Some((_, trait_path, _)) = &item_impl.trait_ {
+                errors.push(
+                    Error::custom("builders can only be generated in inherent `impl` blocks")
+                        .with_span(trait_path),
+                );
+                return errors.finish_with(TokenStream::new());
+            }
+
+            let self_ident = match &*item_impl.self_ty {
+                syn::Type::Path(ty) if ty.qself.is_none() => {
+                    &ty.path.segments.last().expect("paths are not empty").ident
+                }
+                other => {
+                    errors.push(
+                        Error::custom(
+                            "builders can only be generated in `impl` blocks of named types",
+                        )
+                        .with_span(other),
+                    );
+                    return errors.finish_with(TokenStream::new());
+                }
+            };
+            let context = ImplContext {
+                self_ty: &item_impl.self_ty,
+                self_ident,
+                generics: &item_impl.generics,
+            };
+
+            let mut tokens = TokenStream::new();
+            let mut entry_fns = Vec::new();
+            let mut found = false;
+            for impl_item in &mut item_impl.items {
+                if let syn::ImplItem::Fn(method) = impl_item {
+                    let args = match take_builder_attr(&mut method.attrs) {
+                        Some(args) => args,
+                        None => continue,
+                    };
+                    found = true;
+                    if let Some(args) = errors.handle(args) {
+                        let builder = builder_for_fn(
+                            args,
+                            &method.vis,
+                            &method.attrs,
+                            &mut method.sig,
+                            Some(&context),
+                        );
+                        if let Some(builder) = errors.handle(builder) {
+                            tokens.extend(builder.tokens);
+                            if let Some(entry_fn) = builder.entry_fn {
+                                // The method is only called by the builder from now on
+                                method.vis = syn::Visibility::Inherited;
+                                entry_fns.push(syn::ImplItem::Fn(entry_fn));
+                            }
+                        }
+                    }
+                }
+            }
+            item_impl.items.extend(entry_fns);
+
+            if !found {
+                errors.push(
+                    Error::custom(
+                        "mark the associated functions which get a builder with `#[builder]`",
+                    )
+                    .with_span(&item_impl.impl_token),
+                );
+            }
+
+            errors.finish_with(tokens)
+        }
+        other => Err(
+            Error::custom("`#[builder]` can only be used on functions and `impl` blocks")
+                .with_span(other),
+        ),
+    }
+}
+
+/// The generated builder of a function.
+struct FnBuilder {
+    /// The builder struct, its `impl` blocks and its error type.
+    tokens: TokenStream,
+    /// Method which creates the builder from the receiver it borrows, if the function is
+    /// a method. It replaces the method, which is renamed.
+    entry_fn: Option<syn::ImplItemFn>,
+}
+
+/// Generate the builder of a function, removing the builder attributes of its parameters.
+///
+/// If the function is a method, it is renamed to `__{name}`, as the builder is created by a
+/// method with its name.
+fn builder_for_fn(
+    args: TokenStream,
+    vis: &syn::Visibility,
+    attrs: &[syn::Attribute],
+    sig: &mut syn::Signature,
+    context: Option<&ImplContext>,
+) -> darling::Result<FnBuilder> {
+    let mut errors = Error::accumulator();
+    let crate_root = crate_root(&args);
+    let self_ty = context.map(|c| c.self_ty);
+    let receiver_lifetime: syn::Lifetime = parse_quote!('__receiver);
+    let mut has_receiver = false;
+
+    let mut fields = Vec::new();
+    for input in &mut sig.inputs {
+        let param = match input {
+            syn::FnArg::Typed(param) => param,
+            syn::FnArg::Receiver(receiver) => {
+                let is_shared_ref = matches!(receiver.reference, Some((_, None)))
+                    && receiver.mutability.is_none()
+                    && receiver.colon_token.is_none();
+                match self_ty {
+                    Some(self_ty) if is_shared_ref => {
+                        has_receiver = true;
+                        fields.push(receiver_field(&crate_root, self_ty, &receiver_lifetime));
+                    }
+                    _ => errors.push(
+                        Error::custom("builders of methods only support a `&self` receiver")
+                            .with_span(receiver),
+                    ),
+                }
+                continue;
+            }
+        };
+
+        let (attrs, kept): (Vec<_>, Vec<_>) = param
+            .attrs
+            .drain(..)
+            .partition(|attr| PARAM_ATTRS.iter().any(|name| attr.path().is_ident(name)));
+        param.attrs = kept;
+
+        let ident = match &*param.pat {
+            syn::Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => &pat.ident,
+            other => {
+                errors.push(
+                    Error::custom("parameters of functions with a builder must be identifiers")
+                        .with_span(other),
+                );
+                continue;
+            }
+        };
+
+        if let syn::Type::ImplTrait(ty) = &*param.ty {
+            errors.push(
+                Error::custom(
+                    "`impl Trait` parameters are not supported; use a generic parameter instead",
+                )
+                .with_span(ty),
+            );
+            continue;
+        }
+
+        let ty = replace_self(param.ty.to_token_stream(), self_ty);
+        fields.push(quote!(#(#attrs)* #ident: #ty));
+    }
+
+    let fn_ident = sig.ident.clone();
+    let called_ident = if has_receiver {
+        format_ident!("__{}", fn_ident.unraw(), span = fn_ident.span())
+    } else {
+        fn_ident.clone()
+    };
+    let output = match &sig.output {
+        syn::ReturnType::Default => parse_quote!(()),
+        syn::ReturnType::Type(_, ty) => syn::parse2(replace_self(ty.to_token_stream(), self_ty))?,
+    };
+    let is_async = sig.asyncness.is_some();
+    let (ident, target_fn) = match context {
+        None => (
+            syn::Ident::new(&camel_case(&fn_ident), fn_ident.span()),
+            TargetFn {
+                path: fn_ident.to_token_stream(),
+                output,
+                name: fn_ident.unraw().to_string(),
+                is_async,
+                receiver_lifetime: None,
+            },
+        ),
+        Some(context) => {
+            let self_ident = context.self_ident;
+            let ident = if fn_ident == "new" {
+                self_ident.clone()
+            } else {
+                format_ident!(
+                    "{}{}",
+                    self_ident,
+                    camel_case(&fn_ident),
+                    span = fn_ident.span()
+                )
+            };
+            let self_ty = context.self_ty;
+            (
+                ident,
+                TargetFn {
+                    path: quote!(<#self_ty>::#called_ident),
+                    output,
+                    name: format!("{}::{}", self_ident, fn_ident.unraw()),
+                    is_async,
+                    receiver_lifetime: has_receiver.then(|| receiver_lifetime.clone()),
+                },
+            )
+        }
+    };
+
+    let mut generics = merge_generics(context.map(|c| c.generics), &sig.generics);
+    if has_receiver {
+        generics
+            .params
+            .insert(0, syn::LifetimeParam::new(receiver_lifetime.clone()).into());
+        generics.lt_token = Some(Default::default());
+        generics.gt_token = Some(Default::default());
+    }
+    let where_clause = &generics.where_clause;
+    let options_attr = (!args.is_empty()).then(|| quote!(#[builder(#args)]));
+    let ast: syn::DeriveInput = parse_quote!(
+        #options_attr
+        #vis struct #ident #generics #where_clause {
+            #(#fields,)*
+        }
+    );
+
+    let options = errors.handle(Options::from_fn(&ast, target_fn));
+    errors.finish()?;
+    let options = options.expect("errors are returned above");
+
+    let entry_fn = if has_receiver {
+        sig.ident = called_ident;
+
+        let builder_ident = options.builder_ident();
+        let create_empty = options.create_empty();
+        let (_, ty_generics, _) = generics.split_for_impl();
+        let mut entry_generics = sig.generics.clone();
+        entry_generics
+            .params
+            .insert(0, syn::LifetimeParam::new(receiver_lifetime.clone()).into());
+        entry_generics.lt_token = Some(Default::default());
+        entry_generics.gt_token = Some(Default::default());
+        let entry_where_clause = &entry_generics.where_clause;
+        let docs = attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+        let receiver_ident = syn::Ident::new(RECEIVER_FIELD, Span::call_site());
+
+        Some(parse_quote!(
+            #(#docs)*
+            #vis fn #fn_ident #entry_generics(&#receiver_lifetime self) -> #builder_ident #ty_generics #entry_where_clause {
+                let mut builder = #builder_ident::#create_empty();
+                builder.#receiver_ident = #crate_root::export::core::option::Option::Some(self);
+                builder
+            }
+        ))
+    } else {
+        None
+    };
+
+    Ok(FnBuilder {
+        tokens: options.as_builder().into_token_stream(),
+        entry_fn,
+    })
+}
+
+/// Get the path to the root of the derive_builder crate from the `crate` option in `args`.
+///
+/// The receiver field needs it before the options are parsed; if `args` are invalid, the
+/// default is returned and the errors are reported when the options are parsed.
+fn crate_root(args: &TokenStream) -> syn::Path {
+    NestedMeta::parse_meta_list(args.clone())
+        .ok()
+        .and_then(|items| {
+            items.into_iter().find_map(|item| match item {
+                NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.is_ident("crate") => {
+                    syn::Path::from_expr(&nv.value).ok()
+                }
+                _ => None,
+            })
+        })
+        .unwrap_or_else(default_crate_root)
+}
+
+/// The builder field which stores the receiver of a method.
+///
+/// It is set by the method which creates the builder, so the builder has no `Default` impl.
+fn receiver_field(
+    crate_root: &syn::Path,
+    self_ty: &syn::Type,
+    lifetime: &syn::Lifetime,
+) -> TokenStream {
+    let receiver_ident = syn::Ident::new(RECEIVER_FIELD, Span::call_site());
+    let field_ty = syn::LitStr::new(
+        &quote!(#crate_root::export::core::option::Option<&#lifetime #self_ty>).to_string(),
+        self_ty.span(),
+    );
+    let build = syn::LitStr::new(
+        &quote!(match self.#receiver_ident {
+            #crate_root::export::core::option::Option::Some(receiver) => receiver,
+            #crate_root::export::core::option::Option::None => #crate_root::export::core::unreachable!("the receiver is set when the builder is created"),
+        })
+        .to_string(),
+        self_ty.span(),
+    );
+    quote!(
+        #[builder(setter(custom, clear = false), getter = false, field(ty = #field_ty, build = #build))]
+        #receiver_ident: &#lifetime #self_ty
+    )
+}
+
+/// Remove the `#[builder]` attribute which marks an associated function, returning its
+/// arguments.
+fn take_builder_attr(attrs: &mut Vec<syn::Attribute>) -> Option<darling::Result<TokenStream>> {
+    let position = attrs
+        .iter()
+        .position(|attr| attr.path().is_ident("builder"))?;
+    Some(match attrs.remove(position).meta {
+        syn::Meta::Path(_) => Ok(TokenStream::new()),
+        syn::Meta::List(list) => Ok(list.tokens),
+        syn::Meta::NameValue(meta) => Err(Error::unsupported_format("name value").with_span(&meta)),
+    })
+}
+
+/// Combine the generics of an `impl` block and of one of its functions, keeping lifetimes
+/// ahead of the other parameters.
+fn merge_generics(
+    impl_generics: Option<&syn::Generics>,
+    fn_generics: &syn::Generics,
+) -> syn::Generics {
+    let mut generics = syn::Generics::default();
+    let all = || impl_generics.into_iter().chain(Some(fn_generics));
+    let (lifetimes, others): (Vec<_>, Vec<_>) = all()
+        .flat_map(|g| g.params.iter().cloned())
+        .partition(|param| matches!(param, syn::GenericParam::Lifetime(_)));
+    generics.params.extend(lifetimes);
+    generics.params.extend(others);
+
+    for where_clause in all().filter_map(|g| g.where_clause.as_ref()) {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(where_clause.predicates.iter().cloned());
+    }
+
+    if !generics.params.is_empty() {
+        generics.lt_token = Some(Default::default());
+        generics.gt_token = Some(Default::default());
+    }
+
+    generics
+}
+
+/// Replace `Self` with the type of the `impl` block, as it refers to the builder in the
+/// generated code.
+fn replace_self(tokens: TokenStream, self_ty: Option<&syn::Type>) -> TokenStream {
+    let self_ty = match self_ty {
+        Some(self_ty) => self_ty,
+        None => return tokens,
+    };
+
+    tokens
+        .into_iter()
+        .flat_map(|tt| match tt {
+            TokenTree::Ident(ident) if ident == "Self" => self_ty.to_token_stream(),
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(
+                    group.delimiter(),
+                    replace_self(group.stream(), Some(self_ty)),
+                );
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            other => other.into(),
+        })
+        .collect()
+}
+
+/// Convert the name of a function to camel case, so `send_request` becomes `SendRequest`.
+fn camel_case(ident: &syn::Ident) -> String {
+    ident
+        .unraw()
+        .to_string()
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn names() {
+        for (name, expected) in [
+            ("connect", "Connect"),
+            ("send_request", "SendRequest"),
+            ("_private_fn", "PrivateFn"),
+            ("r#type", "Type"),
+        ] {
+            let ident: syn::Ident = syn::parse_str(name).unwrap();
+            assert_eq!(camel_case(&ident), expected);
+        }
+    }
+
+    #[test]
+    fn self_type() {
+        let self_ty: syn::Type = parse_quote!(Client<T>);
+        let ty: syn::Type = parse_quote!(Option<Self>);
+
+        let replaced: syn::Type =
+            syn::parse2(replace_self(ty.to_token_stream(), Some(&self_ty))).unwrap();
+        assert_eq!(replaced, parse_quote!(Option<Client<T>>));
+    }
+
+    #[test]
+    fn generics() {
+        let impl_generics: syn::Generics = parse_quote!(<T: Clone>);
+        let mut fn_generics: syn::Generics = parse_quote!(<'a, U>);
+        fn_generics.where_clause = Some(parse_quote!(where U: Into<T>));
+
+        let generics = merge_generics(Some(&impl_generics), &fn_generics);
+        let where_clause = &generics.where_clause;
+        assert_eq!(
+            quote!(#generics #where_clause).to_string(),
+            quote!(<'a, T: Clone, U> where U: Into<T>).to_string()
+        );
+    }
+
+    #[test]
+    fn phantom_field() {
+        let generics: syn::Generics = parse_quote!(<'a, T: FromStr, const N: usize>);
+        let phantom = FnPhantomField {
+            crate_root: &parse_quote!(::db),
+            generics: &generics,
+        };
+
+        assert_eq!(
+            phantom.declaration().to_string(),
+            quote!(__fn: ::db::export::core::marker::PhantomData<(&'a (), fn() -> T, [(); N],)>,)
+                .to_string()
+        );
+        assert_eq!(
+            phantom.initializer().to_string(),
+            quote!(__fn: ::db::export::core::marker::PhantomData,).to_string()
+        );
+    }
+
+    #[test]
+    fn crate_root_from_args() {
+        let args = quote!(pattern = "owned", crate = "::db", setter(into));
+        assert_eq!(crate_root(&args), parse_quote!(::db));
+        assert_eq!(crate_root(&quote!(setter(into))), default_crate_root());
+    }
+}
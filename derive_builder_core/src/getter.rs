@@ -0,0 +1,213 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+
+use crate::BuilderFieldType;
+
+/// Getter and is-set query for a field of the builder, implementing `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::Getter;
+/// # fn main() {
+/// #     let getter = default_getter!();
+/// #
+/// #     assert_eq!(quote!(#getter).to_string(), quote!(
+/// #[doc = "Returns the value of `foo`, if it has been set."]
+/// pub fn get_foo(&self) -> ::derive_builder::export::core::option::Option<&Foo> {
+///     ::derive_builder::export::core::option::Option::as_ref(&self.foo)
+/// }
+///
+/// #[doc = "Returns `true` if `foo` has been set."]
+/// pub fn is_foo_set(&self) -> bool {
+///     ::derive_builder::export::core::option::Option::is_some(&self.foo)
+/// }
+/// #     ).to_string());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Getter<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this getter.
+    pub getter_enabled: bool,
+    /// Visibility of the getter, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Name of the setter of the field without its prefix, which the getter and the is-set
+    /// query are named after.
+    pub setter_ident: syn::Ident,
+    /// Type of the builder field.
+    ///
+    /// The getter of an `Optional` field returns an `Option` of a reference to the value, and
+    /// is accompanied by an is-set query. The getter of a `Precise` field returns a reference
+    /// to the builder field.
+    pub field_type: BuilderFieldType<'a>,
+}
+
+impl<'a> Getter<'a> {
+    /// Name of the setter in the names of the getter and the is-set query, without the leading
+    /// underscores of e.g. tuple struct fields.
+    fn name(&self) -> String {
+        self.setter_ident
+            .unraw()
+            .to_string()
+            .trim_start_matches('_')
+            .to_string()
+    }
+}
+
+impl<'a> ToTokens for Getter<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.getter_enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let vis = &self.visibility;
+        let field_ident = self.field_ident;
+        let name = self.name();
+        let getter_ident = format_ident!("get_{}", name);
+
+        match self.field_type {
+            BuilderFieldType::Optional(ty) => {
+                let is_set_ident = format_ident!("is_{}_set", name);
+                let getter_doc = format!("Returns the value of `{}`, if it has been set.", name);
+                let is_set_doc = format!("Returns `true` if `{}` has been set.", name);
+
+                tokens.append_all(quote!(
+                    #[doc = #getter_doc]
+                    #vis fn #getter_ident(&self) -> #crate_root::export::core::option::Option<&#ty> {
+                        #crate_root::export::core::option::Option::as_ref(&self.#field_ident)
+                    }
+
+                    #[doc = #is_set_doc]
+                    #vis fn #is_set_ident(&self) -> bool {
+                        #crate_root::export::core::option::Option::is_some(&self.#field_ident)
+                    }
+                ));
+            }
+            BuilderFieldType::Precise(ty) => {
+                let getter_doc = format!("Returns the builder field of `{}`.", name);
+
+                tokens.append_all(quote!(
+                    #[doc = #getter_doc]
+                    #vis fn #getter_ident(&self) -> &#ty {
+                        &self.#field_ident
+                    }
+                ));
+            }
+            BuilderFieldType::Phantom(_) => {}
+        }
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_getter {
+    () => {
+        Getter {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            getter_enabled: true,
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            field_ident: &syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            setter_ident: syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            field_type: BuilderFieldType::Optional(Box::leak(Box::new(parse_quote!(Foo)))),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn optional_field() {
+        let getter = default_getter!();
+
+        assert_eq!(
+            quote!(#getter).to_string(),
+            quote!(
+                #[doc = "Returns the value of `foo`, if it has been set."]
+                pub fn get_foo(&self) -> ::db::export::core::option::Option<&Foo> {
+                    ::db::export::core::option::Option::as_ref(&self.foo)
+                }
+
+                #[doc = "Returns `true` if `foo` has been set."]
+                pub fn is_foo_set(&self) -> bool {
+                    ::db::export::core::option::Option::is_some(&self.foo)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn precise_field() {
+        let ty = parse_quote!(Vec<u8>);
+        let field_ident = syn::Ident::new("_0", ::proc_macro2::Span::call_site());
+        let mut getter = default_getter!();
+        getter.visibility = ::std::borrow::Cow::Owned(syn::Visibility::Inherited);
+        getter.field_ident = &field_ident;
+        getter.setter_ident = field_ident.clone();
+        getter.field_type = BuilderFieldType::Precise(&ty);
+
+        assert_eq!(
+            quote!(#getter).to_string(),
+            quote!(
+                #[doc = "Returns the builder field of `0`."]
+                fn get_0(&self) -> &Vec<u8> {
+                    &self._0
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn renamed_setter() {
+        let mut getter = default_getter!();
+        getter.setter_ident = syn::Ident::new("with_foo", ::proc_macro2::Span::call_site());
+
+        assert_eq!(
+            quote!(#getter).to_string(),
+            quote!(
+                #[doc = "Returns the value of `with_foo`, if it has been set."]
+                pub fn get_with_foo(&self) -> ::db::export::core::option::Option<&Foo> {
+                    ::db::export::core::option::Option::as_ref(&self.foo)
+                }
+
+                #[doc = "Returns `true` if `with_foo` has been set."]
+                pub fn is_with_foo_set(&self) -> bool {
+                    ::db::export::core::option::Option::is_some(&self.foo)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut getter = default_getter!();
+        getter.getter_enabled = false;
+
+        assert_eq!(quote!(#getter).to_string(), "");
+    }
+}
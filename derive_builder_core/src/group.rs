@@ -0,0 +1,144 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+
+use crate::{change_span, field_validation::VALIDATION_ERRORS_NAME};
+
+/// Check of a group of fields declared with `#[builder(group(...))]`, implementing
+/// `quote::ToTokens`.
+///
+/// Lives in the body of `BuildMethod`, before any field is initialized. With
+/// `build_fn(collect_validation_errors)`, the error is added to the `ValidationErrors` of the
+/// build method instead of being returned.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// if let Err(error) = ::derive_builder::GroupRule::ExactlyOne.check(
+///     "auth",
+///     &["token", "password"],
+///     &[self.token.is_some(), self.password.is_some()],
+/// ) {
+///     return Err(Into::into(error));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GroupCheck<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the group, which is reported in the error.
+    pub name: &'a str,
+    /// Name of the `GroupRule` variant of the group, e.g. `ExactlyOne`.
+    pub rule: &'static str,
+    /// Builder fields of the group, in the order they were declared in the group.
+    pub fields: Vec<&'a syn::Ident>,
+    /// Span where the macro was told to use a preexisting error type, which must implement
+    /// `From<GroupViolationError>`.
+    pub custom_error_type_span: Option<Span>,
+    /// Whether the error is added to the `ValidationErrors` of the build method.
+    pub collect_validation_errors: bool,
+}
+
+impl<'a> ToTokens for GroupCheck<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_root = self.crate_root;
+        let name = self.name;
+        let rule = format_ident!("{}", self.rule);
+        let fields = &self.fields;
+        let field_names = fields.iter().map(|field| field.unraw().to_string());
+        let conv_span = self.custom_error_type_span.unwrap_or_else(Span::call_site);
+        // See `MatchNone::ReturnError` for why the span of the crate root is changed
+        let conv_crate_root = change_span(crate_root.into_token_stream(), conv_span);
+        let handle_error = if self.collect_validation_errors {
+            let validation_errors = syn::Ident::new(VALIDATION_ERRORS_NAME, Span::call_site());
+            quote!(
+                #validation_errors.push_message(
+                    #crate_root::export::core::string::ToString::to_string(&error)
+                );
+            )
+        } else {
+            let err_conv = quote_spanned!(conv_span => #conv_crate_root::export::core::convert::Into::into(error));
+            quote!(return #crate_root::export::core::result::Result::Err(#err_conv);)
+        };
+
+        tokens.append_all(quote!(
+            if let #crate_root::export::core::result::Result::Err(error) = #crate_root::GroupRule::#rule.check(
+                #name,
+                &[#(#field_names),*],
+                &[#(self.#fields.is_some()),*],
+            ) {
+                #handle_error
+            }
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn exactly_one() {
+        let token = syn::Ident::new("token", Span::call_site());
+        let password = syn::Ident::new_raw("password", Span::call_site());
+        let check = GroupCheck {
+            crate_root: &parse_quote!(::db),
+            name: "auth",
+            rule: "ExactlyOne",
+            fields: vec![&token, &password],
+            custom_error_type_span: None,
+            collect_validation_errors: false,
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#check).to_string(),
+            quote!(
+                if let ::db::export::core::result::Result::Err(error) = ::db::GroupRule::ExactlyOne.check(
+                    "auth",
+                    &["token", "password"],
+                    &[self.token.is_some(), self.r#password.is_some()],
+                ) {
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(error)
+                    );
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn collect_validation_errors() {
+        let token = syn::Ident::new("token", Span::call_site());
+        let password = syn::Ident::new("password", Span::call_site());
+        let check = GroupCheck {
+            crate_root: &parse_quote!(::db),
+            name: "auth",
+            rule: "AtLeastOne",
+            fields: vec![&token, &password],
+            custom_error_type_span: None,
+            collect_validation_errors: true,
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#check).to_string(),
+            quote!(
+                if let ::db::export::core::result::Result::Err(error) = ::db::GroupRule::AtLeastOne.check(
+                    "auth",
+                    &["token", "password"],
+                    &[self.token.is_some(), self.password.is_some()],
+                ) {
+                    __validation_errors.push_message(
+                        ::db::export::core::string::ToString::to_string(&error)
+                    );
+                }
+            )
+            .to_string()
+        );
+    }
+}
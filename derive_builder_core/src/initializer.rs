@@ -1,7 +1,10 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt};
 
-use crate::{change_span, BlockContents, BuilderPattern, DefaultExpression, DEFAULT_STRUCT_NAME};
+use crate::{
+    change_span, BlockContents, BuilderPattern, DefaultExpression, FieldValidation,
+    DEFAULT_STRUCT_NAME,
+};
 
 /// Initializer for the target struct fields, implementing `quote::ToTokens`.
 ///
@@ -38,6 +41,15 @@ pub struct Initializer<'a> {
     pub crate_root: &'a syn::Path,
     /// Name of the target field.
     pub field_ident: &'a syn::Ident,
+    /// Position of the target field, if the target is a tuple struct.
+    ///
+    /// The target field is then initialized by its index, and `field_ident` only names the
+    /// builder field.
+    pub field_index: Option<&'a syn::Index>,
+    /// Whether the value is an argument of the function called by the build method, or is bound
+    /// to a variable because the target struct has computed fields, rather than a field of the
+    /// target struct. The value is then emitted without the field name.
+    pub fn_argument: bool,
     /// Whether the builder implements a setter for this field.
     pub field_enabled: bool,
     /// How the build method takes and returns `self` (e.g. mutably).
@@ -62,12 +74,17 @@ pub struct Initializer<'a> {
     ///
     /// For sub-builder fields, this will be `build` (or similar)
     pub conversion: FieldConversion<'a>,
+    /// Whether the field is known to be set by the time the initializer runs, because the
+    /// build method has already checked it or the typestate of the builder guarantees it.
+    pub known_initialized: bool,
+    /// Checks of the final value of the field, which is bound to `value`.
+    pub validation: Option<FieldValidation<'a>>,
 }
 
 impl<'a> ToTokens for Initializer<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let struct_field = &self.field_ident;
-        let builder_field = struct_field;
+        let struct_field = self.struct_field();
+        let builder_field = self.field_ident;
 
         // This structure prevents accidental failure to add the trailing `,` due to incautious `return`
         let append_rhs = |tokens: &mut TokenStream| {
@@ -96,13 +113,34 @@ impl<'a> ToTokens for Initializer<'a> {
             }
         };
 
-        tokens.append_all(quote!(#struct_field:));
-        append_rhs(tokens);
+        if !self.fn_argument {
+            tokens.append_all(quote!(#struct_field:));
+        }
+        match &self.validation {
+            Some(validation) => {
+                let mut rhs = TokenStream::new();
+                append_rhs(&mut rhs);
+                tokens.append_all(quote!({
+                    let value = #rhs;
+                    #validation
+                    value
+                }));
+            }
+            None => append_rhs(tokens),
+        }
         tokens.append_all(quote!(,));
     }
 }
 
 impl<'a> Initializer<'a> {
+    /// The target field, by name or by index.
+    fn struct_field(&self) -> syn::Member {
+        match self.field_index {
+            Some(index) => syn::Member::Unnamed(index.clone()),
+            None => syn::Member::Named(self.field_ident.clone()),
+        }
+    }
+
     /// To be used inside of `#struct_field: match self.#builder_field { ... }`
     fn match_some(&'a self) -> MatchSome<'a> {
         match self.builder_pattern {
@@ -122,7 +160,12 @@ impl<'a> Initializer<'a> {
             },
             None => {
                 if self.use_default_struct {
-                    MatchNone::UseDefaultStructField(self.field_ident)
+                    MatchNone::UseDefaultStructField(self.struct_field())
+                } else if self.known_initialized {
+                    MatchNone::Unreachable {
+                        crate_root: self.crate_root,
+                        field_name: self.field_ident.to_string(),
+                    }
                 } else {
                     MatchNone::ReturnError {
                         crate_root: self.crate_root,
@@ -140,8 +183,8 @@ impl<'a> Initializer<'a> {
             Some(expr) => expr.with_crate_root(crate_root).into_token_stream(),
             None if self.use_default_struct => {
                 let struct_ident = syn::Ident::new(DEFAULT_STRUCT_NAME, Span::call_site());
-                let field_ident = self.field_ident;
-                quote!(#struct_ident.#field_ident)
+                let struct_field = self.struct_field();
+                quote!(#struct_ident.#struct_field)
             }
             None => {
                 quote!(#crate_root::export::core::default::Default::default())
@@ -167,16 +210,21 @@ enum MatchNone<'a> {
         expr: &'a DefaultExpression,
         crate_root: &'a syn::Path,
     },
-    /// Inner value must be the field identifier
+    /// Inner value must be the field identifier or index
     ///
     /// The default struct must be in scope in the build_method.
-    UseDefaultStructField(&'a syn::Ident),
+    UseDefaultStructField(syn::Member),
     /// Inner value must be the field name
     ReturnError {
         crate_root: &'a syn::Path,
         field_name: String,
         span: Option<Span>,
     },
+    /// A missing value has already been ruled out
+    Unreachable {
+        crate_root: &'a syn::Path,
+        field_name: String,
+    },
 }
 
 impl<'a> ToTokens for MatchNone<'a> {
@@ -186,10 +234,10 @@ impl<'a> ToTokens for MatchNone<'a> {
                 let expr = expr.with_crate_root(crate_root);
                 tokens.append_all(quote!(None => #expr));
             }
-            MatchNone::UseDefaultStructField(field_ident) => {
+            MatchNone::UseDefaultStructField(ref field_member) => {
                 let struct_ident = syn::Ident::new(DEFAULT_STRUCT_NAME, Span::call_site());
                 tokens.append_all(quote!(
-                    None => #struct_ident.#field_ident
+                    None => #struct_ident.#field_member
                 ))
             }
             MatchNone::ReturnError {
@@ -210,6 +258,15 @@ impl<'a> ToTokens for MatchNone<'a> {
                     None => return #crate_root::export::core::result::Result::Err(#err_conv)
                 ));
             }
+            MatchNone::Unreachable {
+                ref field_name,
+                crate_root,
+            } => {
+                let msg = format!("`{}` is known to be initialized", field_name);
+                tokens.append_all(quote!(
+                    None => #crate_root::export::core::unreachable!(#msg)
+                ));
+            }
         }
     }
 }
@@ -244,12 +301,16 @@ macro_rules! default_initializer {
             // that all test cases are passing crate_root through properly.
             crate_root: &parse_quote!(::db),
             field_ident: &syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            field_index: None,
+            fn_argument: false,
             field_enabled: true,
             builder_pattern: BuilderPattern::Mutable,
             default_value: None,
             use_default_struct: false,
             conversion: FieldConversion::OptionOrDefault,
             custom_error_type_span: None,
+            known_initialized: false,
+            validation: None,
         }
     };
 }
@@ -258,6 +319,7 @@ macro_rules! default_initializer {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use crate::FieldValidators;
 
     #[test]
     fn immutable() {
@@ -351,6 +413,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn known_initialized() {
+        let mut initializer = default_initializer!();
+        initializer.builder_pattern = BuilderPattern::Owned;
+        initializer.known_initialized = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(value) => value,
+                    None => ::db::export::core::unreachable!("`foo` is known to be initialized"),
+                },
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn tuple_field() {
+        let index = syn::Index::from(1);
+        let mut initializer = default_initializer!();
+        initializer.field_index = Some(&index);
+        initializer.use_default_struct = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                1: match self.foo {
+                    Some(ref value) => ::db::export::core::clone::Clone::clone(value),
+                    None => __default.1,
+                },
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn fn_argument() {
+        let mut initializer = default_initializer!();
+        initializer.fn_argument = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(match self.foo {
+                Some(ref value) => ::db::export::core::clone::Clone::clone(value),
+                None =>
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::UninitializedFieldError::from("foo")
+                        )
+                    ),
+            },)
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn validation() {
+        let validators = FieldValidators {
+            non_empty: darling::util::Flag::present(),
+            ..Default::default()
+        };
+        let default_value = DefaultExpression::explicit::<syn::Expr>(parse_quote!("foo".into()));
+        let mut initializer = default_initializer!();
+        initializer.builder_pattern = BuilderPattern::Owned;
+        initializer.default_value = Some(&default_value);
+        initializer.validation = Some(FieldValidation {
+            crate_root: initializer.crate_root,
+            field_name: "foo".to_string(),
+            validators: Some(&validators),
+            each: None,
+            each_in_option: false,
+            custom_error_type_span: None,
+            collect: false,
+        });
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: {
+                    let value = match self.foo {
+                        Some(value) => value,
+                        None => { "foo".into() },
+                    };
+                    if value.is_empty() {
+                        return ::db::export::core::result::Result::Err(
+                            ::db::export::core::convert::Into::into(
+                                ::db::FieldValidationError::new("foo", "must not be empty")
+                            )
+                        );
+                    }
+                    value
+                },
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn setter_disabled() {
         let mut initializer = default_initializer!();
@@ -0,0 +1,123 @@
+use proc_macro2::TokenStream;
+
+/// Implementation of `IntoFuture` for the builder of an `async` function, which awaits its
+/// build method.
+///
+/// The boxed future is not required to be `Send`, so functions whose future is not `Send`
+/// can be awaited as well. The build method returns the unboxed future, which is `Send`
+/// whenever the function's future is.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// impl ::derive_builder::export::core::future::IntoFuture for FooBuilder where Self: 'static {
+///     type Output = Result<Foo, FooBuilderError>;
+///     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'static>>;
+///
+///     fn into_future(self) -> Self::IntoFuture {
+///         Box::pin(async move { self.build().await })
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct IntoFuture<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the `async` build method which is awaited.
+    pub build_fn: &'a syn::Ident,
+    /// Output of the build method.
+    pub output: TokenStream,
+    /// Lifetime of the future, which is the lifetime of the receiver if the builder borrows one.
+    pub lifetime: syn::Lifetime,
+}
+
+impl<'a> IntoFuture<'a> {
+    /// Implement `IntoFuture` for `self_ty`, which is the builder type with the build method.
+    pub fn impl_for(
+        &self,
+        impl_generics: syn::ImplGenerics,
+        self_ty: TokenStream,
+        where_clause: Option<&syn::WhereClause>,
+    ) -> TokenStream {
+        let crate_root = self.crate_root;
+        let build_fn = self.build_fn;
+        let output = &self.output;
+        let lifetime = &self.lifetime;
+        let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+        where_clause.predicates.push(parse_quote!(Self: #lifetime));
+
+        quote!(
+            impl #impl_generics #crate_root::export::core::future::IntoFuture for #self_ty #where_clause {
+                type Output = #output;
+                type IntoFuture = #crate_root::export::core::pin::Pin<
+                    #crate_root::export::core::boxed::Box<
+                        dyn #crate_root::export::core::future::Future<Output = Self::Output> + #lifetime
+                    >
+                >;
+
+                fn into_future(self) -> Self::IntoFuture {
+                    #crate_root::export::core::boxed::Box::pin(async move { self.#build_fn().await })
+                }
+            }
+        )
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_into_future {
+    () => {
+        IntoFuture {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            build_fn: &syn::Ident::new("build", ::proc_macro2::Span::call_site()),
+            output: quote!(Result<Foo, FooBuilderError>),
+            lifetime: parse_quote!('static),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn receiver_lifetime() {
+        let mut into_future = default_into_future!();
+        into_future.lifetime = parse_quote!('__receiver);
+        let mut generics: syn::Generics = parse_quote!(<'__receiver, T: Clone>);
+        generics.where_clause = Some(parse_quote!(where T: Send));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        assert_eq!(
+            into_future
+                .impl_for(impl_generics, quote!(FooBuilder #ty_generics), where_clause)
+                .to_string(),
+            quote!(
+                impl<'__receiver, T: Clone> ::db::export::core::future::IntoFuture for FooBuilder<'__receiver, T>
+                where
+                    T: Send,
+                    Self: '__receiver
+                {
+                    type Output = Result<Foo, FooBuilderError>;
+                    type IntoFuture = ::db::export::core::pin::Pin<
+                        ::db::export::core::boxed::Box<
+                            dyn ::db::export::core::future::Future<Output = Self::Output> + '__receiver
+                        >
+                    >;
+
+                    fn into_future(self) -> Self::IntoFuture {
+                        ::db::export::core::boxed::Box::pin(async move { self.build().await })
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+}
@@ -34,32 +34,74 @@ mod build_method;
 mod builder;
 mod builder_field;
 mod change_span;
+mod clear;
 mod default_expression;
 mod doc_comment;
+mod field_validation;
+mod from_target;
+mod function;
+mod getter;
+mod group;
 mod initializer;
+mod into_future;
 mod macro_options;
 mod options;
 mod setter;
+mod typestate;
+mod variant;
 
 pub(crate) use block::BlockContents;
-pub(crate) use build_method::BuildMethod;
+pub(crate) use build_method::{BuildMethod, ComputedField};
 pub(crate) use builder::Builder;
 pub(crate) use builder_field::{BuilderField, BuilderFieldType};
 pub(crate) use change_span::change_span;
+pub(crate) use clear::{Clear, Reset};
 use darling::FromDeriveInput;
 pub(crate) use default_expression::DefaultExpression;
 pub(crate) use doc_comment::doc_comment_from;
+pub(crate) use field_validation::FieldValidation;
+pub(crate) use from_target::FromTarget;
+pub(crate) use function::{FnPhantomField, TargetFn};
+pub(crate) use getter::Getter;
+pub(crate) use group::GroupCheck;
 pub(crate) use initializer::{FieldConversion, Initializer};
-pub(crate) use options::{BuilderPattern, Each};
+pub(crate) use into_future::IntoFuture;
+pub(crate) use options::{
+    Bounds, BuilderPattern, Each, FieldGroup, FieldValidators, Flatten, SetterTransform,
+};
 use quote::ToTokens;
-pub(crate) use setter::Setter;
+pub(crate) use setter::{FlattenedSetter, Setter, TrySetterErrorsField};
+pub(crate) use typestate::{typestate_param, Typestate};
+pub(crate) use variant::{variant_fn_ident, VariantBuilderFn, VariantPhantomField};
 
 const DEFAULT_STRUCT_NAME: &str = "__default";
 
-/// Derive a builder for a struct
+/// Derive a builder for a struct, or for each variant of an enum
 pub fn builder_for_struct(ast: syn::DeriveInput) -> proc_macro2::TokenStream {
     match macro_options::Options::from_derive_input(&ast) {
-        Ok(val) => val.as_builder().into_token_stream(),
+        Ok(val) => {
+            let mut tokens = val.as_builder().into_token_stream();
+            val.as_from_target().to_tokens(&mut tokens);
+            for variant in val.variants() {
+                variant.as_builder().to_tokens(&mut tokens);
+            }
+            tokens
+        }
         Err(err) => err.write_errors(),
     }
 }
+
+/// Generate a builder for the parameters of a function, or of each associated function of an
+/// `impl` block which is marked with `#[builder]`
+pub fn builder_for_fn(
+    args: proc_macro2::TokenStream,
+    mut item: syn::Item,
+) -> proc_macro2::TokenStream {
+    let builders = function::builders_for_item(args, &mut item);
+    let mut tokens = item.into_token_stream();
+    match builders {
+        Ok(builders) => builders.to_tokens(&mut tokens),
+        Err(err) => err.write_errors().to_tokens(&mut tokens),
+    }
+    tokens
+}
@@ -1,18 +1,21 @@
 use std::convert::TryFrom;
-use std::iter;
 use std::{borrow::Cow, vec::IntoIter};
 
-use crate::{doc_comment_from, BuildMethod};
+use crate::setter::extract_type_from_option;
+use crate::{doc_comment_from, variant_fn_ident, BuildMethod, FromTarget, IntoFuture, TargetFn};
 
-use darling::util::{Flag, PathList, SpannedValue};
-use darling::{Error, FromMeta};
+use darling::util::{Flag, Override, PathList, SpannedValue};
+use darling::{Error, FromDeriveInput, FromMeta};
 use proc_macro2::Span;
-use quote::ToTokens;
-use syn::{spanned::Spanned, Attribute, Generics, Ident, Meta, Path};
+use quote::{format_ident, ToTokens};
+use syn::{ext::IdentExt, spanned::Spanned, Attribute, Generics, Ident, Meta, Path};
 
 use crate::{
-    BlockContents, Builder, BuilderField, BuilderFieldType, BuilderPattern, DefaultExpression,
-    Each, FieldConversion, Initializer, Setter,
+    typestate_param, BlockContents, Builder, BuilderField, BuilderFieldType, BuilderPattern, Clear,
+    ComputedField, DefaultExpression, Each, FieldConversion, FieldGroup, FieldValidation,
+    FieldValidators, Flatten, FlattenedSetter, FnPhantomField, Getter, GroupCheck, Initializer,
+    Reset, Setter, SetterTransform, TrySetterErrorsField, Typestate, VariantBuilderFn,
+    VariantPhantomField,
 };
 
 #[derive(Debug, Clone)]
@@ -151,6 +154,12 @@ pub struct BuildFn {
     /// Generated builder errors include a `From<String>` impl, so returning a `Result<(), String>`
     /// will work without declaring any additional types or `impl` blocks.
     validate: Option<Path>,
+    /// The path of a function that will be called with a reference to the target type after it
+    /// has been constructed, so that defaults are included in the validation.
+    ///
+    /// For a type `Example`, the signature of this function must be
+    /// `(&Example) -> Result<(), impl Into<ExampleBuilderError>>`.
+    validate_target: Option<Path>,
     #[darling(flatten)]
     visibility: VisibilityAttr,
     /// Either the path to an existing error type that the build method should return or a meta
@@ -176,12 +185,28 @@ pub struct BuildFn {
     /// * If `validate` is specified, then this type must provide a conversion from the specified
     ///   function's error type.
     error: Option<BuildFnError>,
+    /// If present, the build method checks every field before constructing the target and
+    /// reports all uninitialized fields at once using `UninitializedFieldsError`, instead of
+    /// returning an error for the first missing field.
+    ///
+    /// A custom error type must then `impl From<UninitializedFieldsError>`.
+    collect_uninitialized: Flag,
+    /// If present, the build method returns the target type instead of a `Result`, and no
+    /// error type is generated.
+    ///
+    /// Every field must then have a default, so that the build method cannot fail.
+    infallible: Flag,
+    /// If set, validators add their failures to a `ValidationErrors`, which the build method
+    /// returns once every field has been validated, instead of returning the first failure.
+    ///
+    /// The struct-level validator then has the signature `(&FooBuilder, &mut ValidationErrors)`.
+    collect_validation_errors: Flag,
 }
 
 impl BuildFn {
     fn validation_needs_error(self) -> darling::Result<Self> {
         let mut acc = Error::accumulator();
-        if self.validate.is_some() {
+        if self.validate.is_some() || self.validate_target.is_some() {
             if let Some(BuildFnError::Generated(e)) = &self.error {
                 if !*e.validation_error {
                     acc.push(
@@ -204,8 +229,12 @@ impl Default for BuildFn {
             skip: false,
             name: Ident::new("build", Span::call_site()),
             validate: None,
+            validate_target: None,
             visibility: Default::default(),
             error: None,
+            collect_uninitialized: Default::default(),
+            infallible: Default::default(),
+            collect_validation_errors: Default::default(),
         }
     }
 }
@@ -222,6 +251,9 @@ pub struct FieldLevelFieldMeta {
     builder_type: Option<syn::Type>,
     /// Custom builder field method, for making target struct field value
     build: Option<BlockContents>,
+    /// Function or closure which converts the value of the target struct field into the
+    /// custom builder field type, when converting a target value back into a builder.
+    from_target: Option<syn::Expr>,
 }
 
 #[derive(Debug, Clone, Default, FromMeta)]
@@ -256,9 +288,13 @@ pub struct StructLevelSetter {
     ///
     ///
     /// ```
-    strip_option: Option<bool>,
+    #[darling(default, with = parse_strip_option)]
+    strip_option: Option<StripOption>,
     /// If `true`, setters will only be generated for fields that opt-in.
     skip: Option<bool>,
+    /// If `true`, every field gets a method which clears it, and the builder gets a `reset`
+    /// method which clears all fields.
+    clear: Option<bool>,
 }
 
 impl StructLevelSetter {
@@ -311,11 +347,30 @@ pub struct FieldLevelSetter {
     /// If `true`, this setter takes an argument that impls `Into<T>`, where `T` is the type of that field
     /// in the deriving struct. Otherwise, the argument's type will be `T`.
     into: Option<bool>,
-    strip_option: Option<bool>,
+    #[darling(default, with = parse_strip_option)]
+    strip_option: Option<StripOption>,
     skip: Option<bool>,
     custom: Option<bool>,
     #[darling(with = parse_each)]
     each: Option<Each>,
+    /// Closure which computes the value of the field from the arguments of the setter.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// #[derive(Builder)]
+    /// struct Example {
+    ///     #[builder(setter(transform = |host: &str, port: u16| format!("{}:{}", host, port)))]
+    ///     address: String,
+    /// }
+    ///
+    /// let example = ExampleBuilder::default()
+    ///     .address("localhost", 8080)
+    ///     .build()?;
+    /// ```
+    transform: Option<SetterTransform>,
+    /// If `true`, emit a method which clears the field. If `false`, the field is not cleared
+    /// by the `reset` method either.
+    clear: Option<bool>,
 }
 
 impl FieldLevelSetter {
@@ -343,6 +398,7 @@ impl FieldLevelSetter {
             || self.into.is_some()
             || self.strip_option.is_some()
             || self.each.is_some()
+            || self.transform.is_some()
         {
             return Some(true);
         }
@@ -366,6 +422,68 @@ fn field_setter(meta: &Meta) -> darling::Result<FieldLevelSetter> {
     }
 }
 
+/// Options of `strip_option`, which can also be used as a word or set to a `bool`.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct StripOption {
+    /// Whether the setter takes the inner type of an `Option` field.
+    #[darling(skip)]
+    enabled: bool,
+    /// Emit an additional setter which takes an `Option`, named `maybe_{setter}` if this
+    /// is used as a word, e.g. `fallback = "foo_opt"` to name it `foo_opt`.
+    fallback: Option<Override<Ident>>,
+    /// The type wrapped by the `Option`, for field types which are not recognized as an
+    /// `Option`, such as aliases, e.g. `inner = "String"` for `type MaybeString = Option<String>`.
+    inner: Option<syn::Type>,
+}
+
+/// `derive_builder` allows `strip_option` to be used as a word or set to a `bool`, and only
+/// enables it implicitly if it is followed by a list of options.
+fn parse_strip_option(meta: &Meta) -> darling::Result<Option<StripOption>> {
+    if let Meta::List(_) = meta {
+        StripOption::from_meta(meta).map(|strip_option| StripOption {
+            enabled: true,
+            ..strip_option
+        })
+    } else {
+        bool::from_meta(meta).map(|enabled| StripOption {
+            enabled,
+            ..Default::default()
+        })
+    }
+    .map(Some)
+}
+
+/// `flatten` cannot be used as a word, as the setters of the field's builder are not known
+/// to the macro; explain this instead of darling's generic error about the format.
+fn parse_flatten(meta: &Meta) -> darling::Result<Option<SpannedValue<Flatten>>> {
+    if let Meta::Path(path) = meta {
+        return Err(Error::custom(
+            r#"`flatten` requires the setters to forward, e.g. `flatten(setters(attempts = "u32"))`, as the fields of another builder are not known to the macro"#,
+        )
+        .with_span(path));
+    }
+
+    SpannedValue::from_meta(meta).map(Some)
+}
+
+/// Options of `try_setter`, which can also be used as a word.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct TrySetter {
+    /// If present, fallible setters record a failed conversion in the builder and return
+    /// the builder, and the build method returns the first recorded error.
+    defer: Flag,
+}
+
+/// `derive_builder` allows `try_setter` to be used as a word, which enables fallible
+/// setters returning a `Result`.
+fn parse_try_setter(meta: &Meta) -> darling::Result<Option<TrySetter>> {
+    if let Meta::Path(_) = meta {
+        Ok(Some(TrySetter::default()))
+    } else {
+        TrySetter::from_meta(meta).map(Some)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct FieldForwardedAttrs {
     pub field: Vec<Attribute>,
@@ -420,18 +538,60 @@ pub struct Field {
     ///
     /// This property only captures the first two, the third is computed in `FieldWithDefaults`.
     default: Option<DefaultExpression>,
-    try_setter: Flag,
+    #[darling(default, with = parse_try_setter)]
+    try_setter: Option<TrySetter>,
     #[darling(default)]
     field: FieldLevelFieldMeta,
+    /// If present, the builder stores the field's own builder instead of an `Option` of the
+    /// field type, and the build method builds the field by calling that builder's `build`.
+    ///
+    /// The builder type defaults to the field type with `Builder` appended to its name, and can
+    /// be overridden with `field(ty = "...")`.
+    sub_builder: Flag,
+    /// If present, the field is a `sub_builder` and the builder also forwards the listed
+    /// setters of the field's builder, e.g. `flatten(prefix = "retry", setters(attempts = "u32"))`.
+    #[darling(default, with = parse_flatten)]
+    flatten: Option<SpannedValue<Flatten>>,
+    /// Field-level override of the struct-level `getter` option.
+    getter: Option<bool>,
+    /// Validators of the final value of the field, which is checked after defaults are applied.
+    validate: Option<FieldValidators>,
+    /// Expression computing the value of the field in the build method, from references to the
+    /// final values of the fields declared before it. The field has no setter.
+    computed: Option<BlockContents>,
+    /// Position of the field, if it belongs to a tuple struct.
+    ///
+    /// Tuple struct fields are given the ident `_0`, `_1`, etc. in `Options::resolve`, which
+    /// names their builder field and setter.
+    #[darling(skip)]
+    index: Option<syn::Index>,
 }
 
 impl Field {
+    /// Returns whether the final value of this field is checked by the build method.
+    fn has_validators(&self) -> bool {
+        self.validate.is_some()
+            || self
+                .setter
+                .each
+                .as_ref()
+                .map_or(false, Each::has_validators)
+    }
+
     /// Resolve and check (post-parsing) options which come from multiple darling options
     ///
     ///  * Check that we don't have a custom field type or builder *and* a default value
-    fn resolve(self) -> darling::Result<Self> {
+    fn resolve(mut self) -> darling::Result<Self> {
         let mut errors = darling::Error::accumulator();
 
+        // `flatten` implies `sub_builder`, whose errors then point at `flatten`.
+        if let Some(flatten) = &self.flatten {
+            if !self.sub_builder.is_present() {
+                let path = Ident::new("flatten", flatten.span()).into();
+                self.sub_builder = Flag::from_meta(&Meta::Path(path))?;
+            }
+        }
+
         // `default` can be preempted by properties in `field`. Silently ignoring a
         // `default` could cause the direct user of `derive_builder` to see unexpected
         // behavior from the builder, so instead we require that the deriving struct
@@ -466,8 +626,219 @@ impl Field {
             }
         };
 
+        if let Some(transform) = &self.setter.transform {
+            if self.setter.into == Some(true) {
+                errors.push(
+                    darling::Error::custom("`transform` and `into` cannot be used together")
+                        .with_span(&transform.closure),
+                );
+            }
+
+            if self.setter.custom == Some(true) {
+                errors.push(
+                    darling::Error::custom("`transform` and `custom` cannot be used together")
+                        .with_span(&transform.closure),
+                );
+            }
+        }
+
+        if let Some(strip_option) = &self.setter.strip_option {
+            let ty = self.field.builder_type.as_ref().unwrap_or(&self.ty);
+            if strip_option.enabled
+                && strip_option.inner.is_none()
+                && !self.sub_builder.is_present()
+                && extract_type_from_option(ty).is_none()
+            {
+                errors.push(
+                    darling::Error::custom(
+                        r#"`strip_option` requires a field of type `Option<T>`; use `strip_option(inner = "T")` if the type is an alias of `Option<T>`"#,
+                    )
+                    .with_span(ty),
+                );
+            }
+        }
+
+        if let Some(each) = self.setter.each.as_ref() {
+            if each.unique.is_present() && each.max.is_none() {
+                errors.push(
+                    darling::Error::custom(
+                        "`unique` requires `max`, as each item is compared with every earlier one",
+                    )
+                    .with_span(&each.unique.span()),
+                );
+            }
+        }
+
+        if cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+            if self.validate.is_some() {
+                errors.push(
+                    darling::Error::custom("`validate` requires the `alloc` feature")
+                        .with_span(&self.ty),
+                );
+            }
+
+            if let Some(each) = self.setter.each.as_ref().filter(|e| e.has_validators()) {
+                errors.push(
+                    darling::Error::custom("`each` validators require the `alloc` feature")
+                        .with_span(&each.name),
+                );
+            }
+        }
+
+        if let Some(computed) = &self.computed {
+            let conflicts = [
+                ("default", self.default.is_some()),
+                ("sub_builder", self.sub_builder.is_present()),
+                (
+                    "field",
+                    self.field.build.is_some() || self.field.builder_type.is_some(),
+                ),
+                ("validate", self.validate.is_some()),
+                (
+                    "setter",
+                    self.setter.setter_enabled() == Some(true) || self.setter.clear == Some(true),
+                ),
+                ("try_setter", self.try_setter.is_some()),
+                ("getter", self.getter == Some(true)),
+            ];
+            for (option, _) in conflicts.iter().filter(|(_, conflict)| *conflict) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`computed` and `{}` cannot be used together",
+                        option
+                    ))
+                    .with_span(&computed.span()),
+                );
+            }
+        }
+
+        if self.sub_builder.is_present() {
+            if self.default.is_some() {
+                errors.push(
+                    darling::Error::custom(
+                        r#"#[builder(default)] and #[builder(sub_builder)] cannot be used together"#,
+                    )
+                    .with_span(&self.sub_builder.span()),
+                );
+            }
+
+            if let Some(each) = &self.setter.each {
+                errors.push(
+                    darling::Error::custom("`each` setters cannot be used with `sub_builder`")
+                        .with_span(&each.name),
+                );
+            }
+
+            if let Some(transform) = &self.setter.transform {
+                errors.push(
+                    darling::Error::custom("`transform` cannot be used with `sub_builder`")
+                        .with_span(&transform.closure),
+                );
+            }
+
+            if self.setter.into == Some(true) {
+                errors.push(
+                    darling::Error::custom(
+                        "`setter(into)` cannot be used with `sub_builder`, which has no setter",
+                    )
+                    .with_span(&self.sub_builder.span()),
+                );
+            }
+
+            if self.try_setter.is_some() {
+                errors.push(
+                    darling::Error::custom(
+                        "`try_setter` cannot be used with `sub_builder`, which has no setter",
+                    )
+                    .with_span(&self.sub_builder.span()),
+                );
+            }
+
+            if self.field.builder_type.is_none() {
+                match sub_builder_type(&self.ty) {
+                    Some(ty) => self.field.builder_type = Some(ty),
+                    None => errors.push(
+                        darling::Error::custom(
+                            r#"Cannot infer the builder type of this field; use #[builder(field(ty = "..."))]"#,
+                        )
+                        .with_span(&self.ty),
+                    ),
+                }
+            }
+        }
+
         errors.finish_with(self)
     }
+
+    /// Set the conversion of a `sub_builder` field, unless it was customized with
+    /// `field(build = "...")`.
+    ///
+    /// Errors of the field's builder are converted into `SubfieldBuildError`, which records
+    /// the name of the field.
+    fn resolve_sub_builder(&mut self, crate_root: &Path) {
+        if !self.sub_builder.is_present() || self.field.build.is_some() {
+            return;
+        }
+
+        if let Some(ident) = &self.ident {
+            let field_name = ident.unraw().to_string();
+            let build: syn::Expr = parse_quote!(
+                self.#ident
+                    .build()
+                    .map_err(|e| #crate_root::SubfieldBuildError::new(#field_name, e))?
+            );
+            self.field.build = Some(build.into());
+        }
+    }
+
+    /// Make this field default to `None` if it is an `Option` without a default or a custom
+    /// builder field, and note it in the documentation of its setters.
+    fn resolve_optional_option(&mut self) {
+        let has_inner_type = self
+            .setter
+            .strip_option
+            .as_ref()
+            .map_or(false, |strip_option| strip_option.inner.is_some());
+
+        if self.default.is_some()
+            || self.field.build.is_some()
+            || self.field.builder_type.is_some()
+            || (extract_type_from_option(&self.ty).is_none() && !has_inner_type)
+        {
+            return;
+        }
+
+        self.default = Some(DefaultExpression::Trait);
+        self.attrs.setter.push(doc_comment_from(
+            "Optional: defaults to `None` if not set.".to_string(),
+        ));
+    }
+}
+
+/// Get the builder type of a `sub_builder` field: the field type with `Builder` appended to
+/// the last segment of its path, keeping any generic arguments.
+fn sub_builder_type(ty: &syn::Type) -> Option<syn::Type> {
+    match ty {
+        syn::Type::Path(ty_path) if ty_path.qself.is_none() => {
+            let mut ty_path = ty_path.clone();
+            let last = ty_path.path.segments.last_mut()?;
+            last.ident = format_ident!("{}Builder", last.ident.unraw());
+            Some(syn::Type::Path(ty_path))
+        }
+        _ => None,
+    }
+}
+
+/// Data extracted from a variant of an enum deriving `Builder`.
+///
+/// Every variant gets its own builder, which uses the struct-level options of the enum.
+#[derive(Debug, Clone, FromVariant)]
+#[darling(attributes(builder), supports(named, unit))]
+pub struct Variant {
+    ident: Ident,
+    /// The name of the generated builder for this variant. Defaults to `#{enum}#{variant}Builder`.
+    name: Option<Ident>,
+    fields: darling::ast::Fields<Field>,
 }
 
 /// Divide a list of attributes into multiple partially-overlapping output lists.
@@ -549,7 +920,7 @@ fn unnest_from_one_attribute(attr: syn::Attribute) -> darling::Result<Attribute>
     }
 }
 
-fn default_crate_root() -> Path {
+pub(crate) fn default_crate_root() -> Path {
     parse_quote!(::derive_builder)
 }
 
@@ -584,7 +955,8 @@ impl TryFrom<Vec<Attribute>> for StructForwardedAttrs {
 #[darling(
     attributes(builder),
     forward_attrs(cfg, allow, builder_struct_attr, builder_impl_attr),
-    supports(struct_named)
+    supports(struct_named, struct_tuple, struct_newtype, enum_named, enum_unit),
+    and_then = Self::resolve
 )]
 pub struct Options {
     ident: Ident,
@@ -610,8 +982,9 @@ pub struct Options {
     crate_root: Path,
 
     /// Controls how `self` is passed to the builder's setter and build methods.
-    #[darling(default)]
-    pattern: BuilderPattern,
+    ///
+    /// Defaults to `mutable`, or to `owned` for typestate builders.
+    pattern: Option<BuilderPattern>,
 
     #[darling(default)]
     build_fn: BuildFn,
@@ -668,8 +1041,18 @@ pub struct Options {
     #[darling(flatten)]
     visibility: VisibilityAttr,
 
-    /// The parsed body of the derived struct.
-    data: darling::ast::Data<darling::util::Ignored, Field>,
+    /// The parsed body of the derived struct or enum.
+    data: darling::ast::Data<Variant, Field>,
+
+    /// The variant whose builder is generated, if these are the options of one of the
+    /// variant builders of an enum. See `Options::variants`.
+    #[darling(skip)]
+    variant: Option<Variant>,
+
+    /// The function called by the build method, if the builder is generated for the
+    /// parameters of a function rather than derived for a struct. See `Options::from_fn`.
+    #[darling(skip)]
+    target_fn: Option<TargetFn>,
 
     /// If set, the generated implementation will not reference types that are only
     /// available in `std`.
@@ -680,119 +1063,1071 @@ pub struct Options {
 
     /// When present, emit additional fallible setters alongside each regular
     /// setter.
-    try_setter: Flag,
+    ///
+    /// With `try_setter(defer)`, the errors of these setters are returned by the build method.
+    #[darling(default, with = parse_try_setter)]
+    try_setter: Option<TrySetter>,
+
+    /// When present, emit a getter and an is-set query for each field of the builder.
+    getter: Flag,
 
     #[darling(default)]
     field: VisibilityAttr,
+
+    /// If set, the builder tracks in its type which required fields have been set, and
+    /// the build method is only available (and infallible) once all of them are.
+    ///
+    /// A field is required if it has neither a field-level nor a struct-level default and
+    /// does not use a custom builder field type.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// #[derive(Builder)]
+    /// #[builder(typestate)]
+    /// struct Example {
+    ///     name: String,
+    ///     #[builder(default)]
+    ///     nickname: Option<String>,
+    /// }
+    ///
+    /// let example: Example = ExampleBuilder::default()
+    ///     .name("John".to_string())
+    ///     .build();
+    /// ```
+    typestate: Flag,
+
+    /// If set, `From<Target>` is implemented for the builder, so that an existing value can be
+    /// modified through the builder's setters and built again.
+    ///
+    /// Fields with a custom builder field type need `field(from_target = "...")` to convert
+    /// the target field value into the builder field.
+    from_target: Flag,
+
+    /// If set, the target struct gets a `to_builder(&self)` method which clones it into a
+    /// builder. This implies `from_target`, and requires the target struct to be `Clone`.
+    to_builder: Flag,
+
+    /// Groups of fields whose combination of set fields is checked by the build method, e.g.
+    /// `group(name = "auth", exactly_one(token, password))`.
+    #[darling(multiple, rename = "group")]
+    groups: Vec<FieldGroup>,
+
+    /// If set, fields of type `Option<T>` default to `None` unless they have a default, a
+    /// custom builder field type or a `field(build = "...")` conversion. This is redundant
+    /// with a struct-level `default`, whose value is used instead.
+    optional_options: Flag,
+
+    /// If set, the generated error type converts into `SubfieldBuildError`, so that the
+    /// builder can be the `sub_builder` of another builder.
+    nested: Flag,
 }
 
-/// Accessors for parsed properties.
 impl Options {
-    pub fn builder_ident(&self) -> Ident {
-        if let Some(ref custom) = self.name {
-            return custom.clone();
+    /// Check (post-parsing) options which come from multiple darling options.
+    ///
+    ///  * Check that typestate builders use the owned pattern, and that nothing can make their
+    ///    build method fail.
+    ///  * Name the fields of tuple structs after their index.
+    ///  * Set the conversion of `sub_builder` fields, which depends on the crate root.
+    ///  * Make `Option` fields default to `None` with `optional_options`.
+    fn resolve(mut self) -> darling::Result<Self> {
+        let mut errors = Error::accumulator();
+
+        match &mut self.data {
+            darling::ast::Data::Struct(fields) => {
+                if fields.is_tuple() {
+                    for (i, field) in fields.fields.iter_mut().enumerate() {
+                        field.ident = Some(Ident::new(&format!("_{}", i), field.ty.span()));
+                        field.index = Some(syn::Index::from(i));
+                    }
+                }
+
+                for field in &mut fields.fields {
+                    field.resolve_sub_builder(&self.crate_root);
+                }
+            }
+            darling::ast::Data::Enum(variants) => {
+                for field in variants.iter_mut().flat_map(|v| &mut v.fields.fields) {
+                    field.resolve_sub_builder(&self.crate_root);
+                }
+            }
         }
 
-        format_ident!("{}Builder", self.ident)
-    }
+        if self.optional_options.is_present() && self.default.is_none() {
+            let fields: Vec<&mut Field> = match &mut self.data {
+                darling::ast::Data::Struct(fields) => fields.fields.iter_mut().collect(),
+                darling::ast::Data::Enum(variants) => variants
+                    .iter_mut()
+                    .flat_map(|v| &mut v.fields.fields)
+                    .collect(),
+            };
+            for field in fields {
+                field.resolve_optional_option();
+            }
+        }
 
-    pub fn builder_error_ident(&self) -> Path {
-        if let Some(BuildFnError::Existing(existing)) = self.build_fn.error.as_ref() {
-            existing.clone()
-        } else if let Some(ref custom) = self.name {
-            format_ident!("{}Error", custom).into()
-        } else {
-            format_ident!("{}BuilderError", self.ident).into()
+        if self.is_enum() {
+            if let Some(default) = &self.default {
+                errors.push(
+                    Error::custom("struct-level `default` cannot be used on enums")
+                        .with_span(&default.span()),
+                );
+            }
+
+            for flag in [&self.from_target, &self.to_builder] {
+                if flag.is_present() {
+                    errors.push(
+                        Error::custom("`from_target` and `to_builder` cannot be used on enums")
+                            .with_span(&flag.span()),
+                    );
+                }
+            }
         }
-    }
 
-    /// The visibility of the builder struct.
-    /// If a visibility was declared in attributes, that will be used;
-    /// otherwise the struct's own visibility will be used.
-    pub fn builder_vis(&self) -> Cow<'_, syn::Visibility> {
-        self.visibility
-            .to_explicit_visibility()
-            .unwrap_or_else(|| Cow::Borrowed(&self.vis))
-    }
+        if self.converts_from_target() {
+            self.check_from_target_fields(&mut errors);
+        }
 
-    /// Get the visibility of the emitted `build` method.
-    /// This defaults to the visibility of the parent builder, but can be overridden.
-    pub fn build_method_vis(&self) -> Cow<'_, syn::Visibility> {
-        self.build_fn
-            .visibility
-            .to_explicit_visibility()
-            .unwrap_or_else(|| self.builder_vis())
-    }
+        if !self.groups.is_empty() {
+            self.check_groups(&mut errors);
+        }
 
-    pub fn raw_fields(&self) -> Vec<&Field> {
-        self.data
-            .as_ref()
-            .take_struct()
-            .expect("Only structs supported")
-            .fields
-    }
+        if self.build_fn.collect_validation_errors.is_present()
+            && cfg!(not(any(feature = "alloc", feature = "lib_has_std")))
+        {
+            errors.push(
+                Error::custom("`build_fn(collect_validation_errors)` requires the `alloc` feature")
+                    .with_span(&self.build_fn.collect_validation_errors.span()),
+            );
+        }
 
-    /// A builder requires `Clone` to be derived if its build method or any of its setters
-    /// use the mutable or immutable pattern.
-    pub fn requires_clone(&self) -> bool {
-        self.pattern.requires_clone() || self.fields().any(|f| f.pattern().requires_clone())
-    }
+        if self.nested.is_present() {
+            let span = self.nested.span();
+            if self.is_enum() {
+                errors.push(Error::custom("`nested` cannot be used on enums").with_span(&span));
+            } else if self.typestate.is_present() || self.infallible() {
+                errors.push(
+                    Error::custom("`nested` cannot be used with `typestate` or `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(&span),
+                );
+            } else if self.custom_error_type_span().is_some() {
+                errors.push(
+                    Error::custom("`nested` cannot be used with a custom error type; implement `From<...>` for `SubfieldBuildError` instead")
+                        .with_span(&span),
+                );
+            }
 
-    /// Get an iterator over the input struct's fields which pulls fallback
-    /// values from struct-level settings.
-    pub fn fields(&self) -> FieldIter<'_> {
-        FieldIter(self, self.raw_fields().into_iter())
-    }
-}
+            if cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+                errors
+                    .push(Error::custom("`nested` requires the `alloc` feature").with_span(&span));
+            }
+        }
 
-/// Converters to codegen structs
-impl Options {
-    pub fn as_builder(&self) -> Builder<'_> {
-        Builder {
-            crate_root: &self.crate_root,
-            enabled: true,
-            ident: self.builder_ident(),
-            pattern: self.pattern,
-            derives: &self.derive,
-            struct_attrs: &self.attrs.struct_attrs,
-            impl_attrs: &self.attrs.impl_attrs,
-            impl_default: !self.custom_constructor.is_present(),
-            create_empty: self.create_empty.clone(),
-            generics: Some(&self.generics),
-            visibility: self.builder_vis(),
-            fields: self
-                .fields()
-                .map(|f| f.as_builder_field().into_token_stream())
-                .collect(),
-            field_initializers: self
-                .fields()
-                .map(|f| f.as_builder_field().default_initializer_tokens())
-                .collect(),
-            functions: self
-                .fields()
-                .map(|f| f.as_setter().into_token_stream())
-                .chain(iter::once(self.as_build_method().into_token_stream()))
-                .collect(),
-            generate_error: self
-                .build_fn
-                .error
-                .as_ref()
-                .and_then(BuildFnError::as_existing)
-                .is_none(),
-            generate_validation_error: self
+        for field in self.fields() {
+            if field.field.setter.clear != Some(true) || field.field.computed.is_some() {
+                continue;
+            }
+
+            if !field.field_enabled() {
+                errors.push(
+                    Error::custom(
+                        "`setter(clear)` cannot be used on a field whose setter is skipped",
+                    )
+                    .with_span(field.field_ident()),
+                );
+            } else if field.typestate_required() {
+                errors.push(
+                    Error::custom(
+                        "`setter(clear)` cannot be used on a required field of a typestate builder, as clearing it would change the typestate; give the field a `default` instead",
+                    )
+                    .with_span(field.field_ident()),
+                );
+            }
+        }
+
+        if let Some(strip_option) = &self.setter.strip_option {
+            if let Some(Override::Explicit(name)) = &strip_option.fallback {
+                errors.push(
+                    Error::custom("`strip_option(fallback)` can only be named on fields")
+                        .with_span(name),
+                );
+            }
+
+            if let Some(inner) = &strip_option.inner {
+                errors.push(
+                    Error::custom("`strip_option(inner)` can only be used on fields")
+                        .with_span(inner),
+                );
+            }
+        }
+
+        if let Some(defer_span) = self.try_setter_defer_span() {
+            if self.infallible() {
+                errors.push(
+                    Error::custom("`try_setter(defer)` cannot be used with `typestate` or `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(&defer_span),
+                );
+            }
+
+            if cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+                errors.push(
+                    Error::custom("`try_setter(defer)` requires the `alloc` feature")
+                        .with_span(&defer_span),
+                );
+            }
+        }
+
+        if self.typestate.is_present() {
+            let typestate_span = self.typestate.span();
+
+            if matches!(self.pattern, Some(p) if p != BuilderPattern::Owned) {
+                errors.push(
+                    Error::custom("`typestate` builders must use the owned pattern")
+                        .with_span(&typestate_span),
+                );
+            }
+
+            if let Some(validate) = &self.build_fn.validate {
+                errors.push(
+                    Error::custom("`build_fn(validate)` cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(validate),
+                );
+            }
+
+            if let Some(validate_target) = &self.build_fn.validate_target {
+                errors.push(
+                    Error::custom("`build_fn(validate_target)` cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(validate_target),
+                );
+            }
+
+            if let Some(BuildFnError::Existing(error)) = &self.build_fn.error {
+                errors.push(
+                    Error::custom("`build_fn(error)` cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(error),
+                );
+            }
+
+            if self.build_fn.collect_uninitialized.is_present() {
+                errors.push(
+                    Error::custom("`build_fn(collect_uninitialized)` cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(&self.build_fn.collect_uninitialized.span()),
+                );
+            }
+
+            if self.build_fn.collect_validation_errors.is_present() {
+                errors.push(
+                    Error::custom("`build_fn(collect_validation_errors)` cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(&self.build_fn.collect_validation_errors.span()),
+                );
+            }
+
+            if self.is_enum() {
+                for variant in self.variants() {
+                    variant.check_typestate_fields(&mut errors);
+                }
+            } else {
+                self.check_typestate_fields(&mut errors);
+            }
+        }
+
+        if self.build_fn.infallible.is_present() {
+            let infallible_span = self.build_fn.infallible.span();
+
+            if self.typestate.is_present() {
+                errors.push(
+                    Error::custom("`build_fn(infallible)` cannot be used with `typestate`, whose build method is already infallible")
+                        .with_span(&infallible_span),
+                );
+            }
+
+            if let Some(validate) = &self.build_fn.validate {
+                errors.push(
+                    Error::custom("`build_fn(validate)` cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(validate),
+                );
+            }
+
+            if let Some(validate_target) = &self.build_fn.validate_target {
+                errors.push(
+                    Error::custom("`build_fn(validate_target)` cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(validate_target),
+                );
+            }
+
+            if let Some(BuildFnError::Existing(error)) = &self.build_fn.error {
+                errors.push(
+                    Error::custom("`build_fn(error)` cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(error),
+                );
+            }
+
+            if self.build_fn.collect_uninitialized.is_present() {
+                errors.push(
+                    Error::custom("`build_fn(collect_uninitialized)` cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(&self.build_fn.collect_uninitialized.span()),
+                );
+            }
+
+            if self.build_fn.collect_validation_errors.is_present() {
+                errors.push(
+                    Error::custom("`build_fn(collect_validation_errors)` cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(&self.build_fn.collect_validation_errors.span()),
+                );
+            }
+
+            if self.is_enum() {
+                for variant in self.variants() {
+                    variant.check_infallible_fields(&mut errors);
+                }
+            } else {
+                self.check_infallible_fields(&mut errors);
+            }
+        }
+
+        errors.finish_with(self)
+    }
+
+    /// Check that no field can make the build method fail, if it is infallible.
+    fn check_infallible_fields(&self, errors: &mut darling::error::Accumulator) {
+        for field in self.fields() {
+            if field.field.sub_builder.is_present() {
+                errors.push(
+                    Error::custom("`sub_builder` cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(&field.field.sub_builder.span()),
+                );
+            } else if field.is_required() {
+                errors.push(
+                    Error::custom("`build_fn(infallible)` requires a default for this field")
+                        .with_span(field.field_ident()),
+                );
+            }
+
+            if field.field.validate.is_some() {
+                errors.push(
+                    Error::custom("`validate` cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(field.field_ident()),
+                );
+            }
+
+            if let Some(each) = field.each_with_validators() {
+                errors.push(
+                    Error::custom("`each` validators cannot be used with `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(&each.name),
+                );
+            }
+        }
+    }
+
+    /// Check that the builder field of every field can be initialized from a target value.
+    fn check_from_target_fields(&self, errors: &mut darling::error::Accumulator) {
+        for field in self.fields() {
+            if field.field_enabled()
+                && field.field.field.builder_type.is_some()
+                && field.field.field.from_target.is_none()
+                && !field.field.sub_builder.is_present()
+            {
+                errors.push(
+                    Error::custom(
+                        r#"fields with a custom builder field type need #[builder(field(from_target = "..."))] to be converted from the target"#,
+                    )
+                    .with_span(field.field_ident()),
+                );
+            }
+        }
+    }
+
+    /// Check that the groups of fields can be checked by the build method.
+    fn check_groups(&self, errors: &mut darling::error::Accumulator) {
+        for group in &self.groups {
+            let (_, fields) = group.rule();
+            let span = fields[0].span();
+
+            if self.is_enum() {
+                errors.push(Error::custom("`group` cannot be used on enums").with_span(&span));
+                continue;
+            }
+
+            if self.typestate.is_present() || self.infallible() {
+                errors.push(
+                    Error::custom("`group` cannot be used with `typestate` or `build_fn(infallible)`, as the build method cannot fail")
+                        .with_span(&span),
+                );
+            }
+
+            if cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+                errors.push(Error::custom("`group` requires the `alloc` feature").with_span(&span));
+            }
+
+            for path in fields {
+                match self.group_field(path) {
+                    Some(field) if matches!(field.field_type(), BuilderFieldType::Optional(_)) => {}
+                    Some(_) => errors.push(
+                        Error::custom(
+                            "fields in a group cannot be skipped or use a custom builder field type",
+                        )
+                        .with_span(path),
+                    ),
+                    None => errors.push(Error::unknown_field_path(path).with_span(path)),
+                }
+            }
+        }
+    }
+
+    /// Parse the options of the builder of a function, whose parameters are the fields of `ast`.
+    pub fn from_fn(ast: &syn::DeriveInput, target_fn: TargetFn) -> darling::Result<Self> {
+        let mut options = Self::from_derive_input(ast)?;
+
+        if let Some(default) = &options.default {
+            return Err(
+                Error::custom("struct-level `default` cannot be used on functions")
+                    .with_span(&default.span()),
+            );
+        }
+
+        for flag in [&options.from_target, &options.to_builder] {
+            if flag.is_present() {
+                return Err(Error::custom(
+                    "`from_target` and `to_builder` cannot be used on functions",
+                )
+                .with_span(&flag.span()));
+            }
+        }
+
+        if let Some(computed) = options.fields().find_map(|f| f.field.computed.as_ref()) {
+            return Err(
+                Error::custom("`computed` cannot be used on functions").with_span(&computed.span())
+            );
+        }
+
+        if target_fn.is_async && cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+            return Err(Error::custom(
+                "builders of `async` functions require the `alloc` feature",
+            ));
+        }
+
+        options.target_fn = Some(target_fn);
+        Ok(options)
+    }
+
+    /// Check that the fields of a typestate builder can be tracked in its type.
+    fn check_typestate_fields(&self, errors: &mut darling::error::Accumulator) {
+        for field in self.fields() {
+            if matches!(field.field.pattern, Some(p) if p != BuilderPattern::Owned) {
+                errors.push(
+                    Error::custom("`typestate` builders must use the owned pattern")
+                        .with_span(field.field_ident()),
+                );
+            }
+
+            if field.field.sub_builder.is_present() {
+                errors.push(
+                    Error::custom("`sub_builder` cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(&field.field.sub_builder.span()),
+                );
+            }
+
+            if field.field.validate.is_some() {
+                errors.push(
+                    Error::custom("`validate` cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(field.field_ident()),
+                );
+            }
+
+            if let Some(each) = field.each_with_validators() {
+                errors.push(
+                    Error::custom("`each` validators cannot be used with `typestate`, as the build method cannot fail")
+                        .with_span(&each.name),
+                );
+            }
+
+            if field.typestate_required() && !field.setter_enabled() {
+                errors.push(
+                    Error::custom(
+                        "fields without a generated setter need a default when using `typestate`",
+                    )
+                    .with_span(field.field_ident()),
+                );
+            }
+        }
+    }
+}
+
+/// Accessors for parsed properties.
+impl Options {
+    pub fn builder_ident(&self) -> Ident {
+        if let Some(ref variant) = self.variant {
+            return variant
+                .name
+                .clone()
+                .unwrap_or_else(|| format_ident!("{}{}Builder", self.ident, variant.ident));
+        }
+
+        if let Some(ref custom) = self.name {
+            return custom.clone();
+        }
+
+        format_ident!("{}Builder", self.ident)
+    }
+
+    /// Get the identifier of the inherent method which creates an empty builder.
+    pub fn create_empty(&self) -> &Ident {
+        &self.create_empty
+    }
+
+    pub fn builder_error_ident(&self) -> Path {
+        if let Some(BuildFnError::Existing(existing)) = self.build_fn.error.as_ref() {
+            existing.clone()
+        } else if let Some(ref custom) = self.name {
+            format_ident!("{}Error", custom).into()
+        } else {
+            format_ident!("{}BuilderError", self.ident).into()
+        }
+    }
+
+    /// The visibility of the builder struct.
+    /// If a visibility was declared in attributes, that will be used;
+    /// otherwise the struct's own visibility will be used.
+    pub fn builder_vis(&self) -> Cow<'_, syn::Visibility> {
+        self.visibility
+            .to_explicit_visibility()
+            .unwrap_or_else(|| Cow::Borrowed(&self.vis))
+    }
+
+    /// Get the visibility of the emitted `build` method.
+    /// This defaults to the visibility of the parent builder, but can be overridden.
+    pub fn build_method_vis(&self) -> Cow<'_, syn::Visibility> {
+        self.build_fn
+            .visibility
+            .to_explicit_visibility()
+            .unwrap_or_else(|| self.builder_vis())
+    }
+
+    /// Check if the build method returns the target type rather than a `Result`, because
+    /// the builder is a typestate builder or uses `build_fn(infallible)`.
+    pub fn infallible(&self) -> bool {
+        self.typestate.is_present() || self.build_fn.infallible.is_present()
+    }
+
+    /// Check if `From<Target>` is implemented for the builder.
+    pub fn converts_from_target(&self) -> bool {
+        self.from_target.is_present() || self.to_builder.is_present()
+    }
+
+    /// Get how `self` is passed to the builder's setter and build methods.
+    ///
+    /// Typestate builders and builders of `async` functions default to the owned pattern, so
+    /// the builder returned by the setters can be built or awaited directly.
+    pub fn pattern(&self) -> BuilderPattern {
+        let is_async = self.target_fn.as_ref().map_or(false, |f| f.is_async);
+        self.pattern
+            .unwrap_or(if self.typestate.is_present() || is_async {
+                BuilderPattern::Owned
+            } else {
+                BuilderPattern::Mutable
+            })
+    }
+
+    /// Get the fields of the builder struct.
+    ///
+    /// The builder of an enum has no fields; the fields of the variants are part of the
+    /// variant builders.
+    pub fn raw_fields(&self) -> Vec<&Field> {
+        self.data
+            .as_ref()
+            .take_struct()
+            .map(|fields| fields.fields)
+            .unwrap_or_default()
+    }
+
+    /// Check if the builder is derived for an enum, rather than for a struct or one of the
+    /// variants of an enum.
+    pub fn is_enum(&self) -> bool {
+        self.data.is_enum()
+    }
+
+    /// Get the options of the builders of each variant, if the builder is derived for an enum.
+    ///
+    /// These are the options of the enum, with the fields of the variant.
+    pub fn variants(&self) -> Vec<Options> {
+        match &self.data {
+            darling::ast::Data::Enum(variants) => variants
+                .iter()
+                .map(|variant| Options {
+                    data: darling::ast::Data::Struct(variant.fields.clone()),
+                    variant: Some(variant.clone()),
+                    ..self.clone()
+                })
+                .collect(),
+            darling::ast::Data::Struct(_) => vec![],
+        }
+    }
+
+    /// Check if any field of the struct, or of the variant, is computed by the build method.
+    fn has_computed_fields(&self) -> bool {
+        self.fields().any(|f| f.field.computed.is_some())
+    }
+
+    /// Check if any field of the struct, or of any variant of the enum, has its own builder.
+    fn has_sub_builder_fields(&self) -> bool {
+        match &self.data {
+            darling::ast::Data::Struct(fields) => fields.iter().any(|f| f.sub_builder.is_present()),
+            darling::ast::Data::Enum(variants) => variants
+                .iter()
+                .flat_map(|v| v.fields.iter())
+                .any(|f| f.sub_builder.is_present()),
+        }
+    }
+
+    /// Get the field named by `path` in a `group`.
+    fn group_field(&self, path: &Path) -> Option<FieldWithDefaults<'_>> {
+        let ident = path.get_ident()?;
+        self.fields()
+            .find(|field| field.field_ident().unraw() == ident.unraw())
+    }
+
+    /// Get the span where the macro was told to use a preexisting error type.
+    fn custom_error_type_span(&self) -> Option<Span> {
+        self.build_fn
+            .error
+            .as_ref()
+            .and_then(BuildFnError::as_existing)
+            .map(|p| p.span())
+    }
+
+    /// Check if any field of the struct, or of any variant of the enum, has validators.
+    fn has_field_validators(&self) -> bool {
+        match &self.data {
+            darling::ast::Data::Struct(fields) => fields.iter().any(Field::has_validators),
+            darling::ast::Data::Enum(variants) => variants
+                .iter()
+                .flat_map(|v| v.fields.iter())
+                .any(Field::has_validators),
+        }
+    }
+
+    /// Get the span of the first `try_setter(defer)`, if the fallible setters of any field
+    /// of the struct, or of any variant of the enum, record their errors in the builder.
+    fn try_setter_defer_span(&self) -> Option<Span> {
+        let defer = |t: &Option<TrySetter>| {
+            t.as_ref()
+                .filter(|t| t.defer.is_present())
+                .map(|t| t.defer.span())
+        };
+        defer(&self.try_setter).or_else(|| match &self.data {
+            darling::ast::Data::Struct(fields) => fields.iter().find_map(|f| defer(&f.try_setter)),
+            darling::ast::Data::Enum(variants) => variants
+                .iter()
+                .flat_map(|v| v.fields.iter())
+                .find_map(|f| defer(&f.try_setter)),
+        })
+    }
+
+    /// Check if the fallible setters of any field record their errors in the builder.
+    fn defers_try_setter_errors(&self) -> bool {
+        self.try_setter_defer_span().is_some()
+    }
+
+    /// Returns the builder field in which fallible setters record their errors, if any
+    /// field of the builder defers them to the build method.
+    fn as_try_setter_errors_field(&self) -> Option<TrySetterErrorsField<'_>> {
+        if self.is_enum() || !self.defers_try_setter_errors() {
+            return None;
+        }
+
+        Some(TrySetterErrorsField {
+            crate_root: &self.crate_root,
+        })
+    }
+
+    /// Name of the built type for documentation, e.g. `Shape::Circle` for a variant builder,
+    /// or of the called function.
+    fn target_name(&self) -> String {
+        if let Some(target_fn) = &self.target_fn {
+            return target_fn.name.clone();
+        }
+
+        match &self.variant {
+            Some(variant) => format!("{}::{}", self.ident, variant.ident),
+            None => self.ident.to_string(),
+        }
+    }
+
+    /// A builder requires `Clone` to be derived if its build method or any of its setters
+    /// use the mutable or immutable pattern.
+    pub fn requires_clone(&self) -> bool {
+        self.pattern().requires_clone() || self.fields().any(|f| f.pattern().requires_clone())
+    }
+
+    /// Get an iterator over the input struct's fields which pulls fallback
+    /// values from struct-level settings.
+    pub fn fields(&self) -> FieldIter<'_> {
+        FieldIter(self, self.raw_fields().into_iter())
+    }
+}
+
+/// Converters to codegen structs
+impl Options {
+    pub fn as_builder(&self) -> Builder<'_> {
+        let typestate = self.as_typestate().map(|typestate| Typestate {
+            build_fn: self.as_build_method().into_token_stream(),
+            ..typestate
+        });
+        let phantom = self.as_variant_phantom_field();
+        let fn_phantom = self.as_fn_phantom_field();
+        let try_setter_errors = self.as_try_setter_errors_field();
+
+        let functions = if self.is_enum() {
+            self.as_variant_builder_fns()
+        } else {
+            self.fields()
+                .flat_map(|f| {
+                    [
+                        f.as_setter().into_token_stream(),
+                        f.as_clear().into_token_stream(),
+                        f.as_getter().into_token_stream(),
+                    ]
+                })
+                .chain(Some(self.as_reset().into_token_stream()))
+                .chain(
+                    typestate
+                        .is_none()
+                        .then(|| self.as_build_method().into_token_stream()),
+                )
+                .collect()
+        };
+
+        let doc_comment = if let Some(target_fn) = &self.target_fn {
+            format!(
+                include_str!("doc_tpl/builder_struct_fn.md"),
+                fn_name = target_fn.name
+            )
+        } else if self.is_enum() {
+            format!(
+                include_str!("doc_tpl/builder_struct_enum.md"),
+                struct_name = self.ident
+            )
+        } else if let Some(variant) = &self.variant {
+            format!(
+                include_str!("doc_tpl/builder_struct_variant.md"),
+                struct_name = self.ident,
+                variant_name = variant.ident
+            )
+        } else {
+            format!(
+                include_str!("doc_tpl/builder_struct.md"),
+                struct_name = self.ident
+            )
+        };
+
+        Builder {
+            crate_root: &self.crate_root,
+            enabled: true,
+            ident: self.builder_ident(),
+            pattern: self.pattern(),
+            derives: &self.derive,
+            struct_attrs: &self.attrs.struct_attrs,
+            impl_attrs: &self.attrs.impl_attrs,
+            // The builder of a method can only be created with the receiver it borrows
+            impl_default: !self.custom_constructor.is_present()
+                && !self
+                    .target_fn
+                    .as_ref()
+                    .map_or(false, |f| f.receiver_lifetime.is_some()),
+            create_empty: self.create_empty.clone(),
+            generics: if self.is_enum() {
+                None
+            } else {
+                Some(&self.generics)
+            },
+            visibility: self.builder_vis(),
+            fields: self
+                .fields()
+                .map(|f| f.as_builder_field().into_token_stream())
+                .chain(phantom.as_ref().map(VariantPhantomField::declaration))
+                .chain(fn_phantom.as_ref().map(FnPhantomField::declaration))
+                .chain(
+                    try_setter_errors
+                        .as_ref()
+                        .map(TrySetterErrorsField::declaration),
+                )
+                .collect(),
+            field_initializers: self
+                .fields()
+                .map(|f| f.as_builder_field().default_initializer_tokens())
+                .chain(phantom.as_ref().map(VariantPhantomField::initializer))
+                .chain(fn_phantom.as_ref().map(FnPhantomField::initializer))
+                .chain(
+                    try_setter_errors
+                        .as_ref()
+                        .map(TrySetterErrorsField::initializer),
+                )
+                .collect(),
+            functions,
+            generate_error: !self.infallible()
+                && self.variant.is_none()
+                && self
+                    .build_fn
+                    .error
+                    .as_ref()
+                    .and_then(BuildFnError::as_existing)
+                    .is_none(),
+            generate_validation_error: self
                 .build_fn
                 .error
                 .as_ref()
                 .and_then(BuildFnError::as_generated)
                 .map(|e| *e.validation_error)
                 .unwrap_or(true),
+            generate_uninitialized_fields_error: self.build_fn.collect_uninitialized.is_present(),
+            generate_subfield_error: self.has_sub_builder_fields(),
+            generate_field_validation_error: self.has_field_validators()
+                && !self.build_fn.collect_validation_errors.is_present(),
+            generate_validation_errors_error: self.build_fn.collect_validation_errors.is_present(),
+            generate_group_violation_error: !self.groups.is_empty(),
+            generate_try_setter_error: self.defers_try_setter_errors(),
+            generate_into_subfield_error: self.nested.is_present(),
             no_alloc: cfg!(not(any(feature = "alloc", feature = "lib_has_std"))),
             must_derive_clone: self.requires_clone(),
-            doc_comment: Some(doc_comment_from(format!(
-                include_str!("doc_tpl/builder_struct.md"),
-                struct_name = self.ident
-            ))),
+            doc_comment: Some(doc_comment_from(doc_comment)),
             std: !self.no_std.is_present(),
+            typestate,
+            into_future: self.as_into_future(),
+        }
+    }
+
+    /// Returns the `Typestate` of the builder, without its build method, if the
+    /// builder tracks required fields in its type.
+    fn as_typestate(&self) -> Option<Typestate<'_>> {
+        if !self.typestate.is_present() || self.is_enum() {
+            return None;
+        }
+
+        Some(Typestate {
+            crate_root: &self.crate_root,
+            builder_ident: self.builder_ident(),
+            generics: Some(&self.generics),
+            params: self
+                .fields()
+                .filter(|f| f.typestate_required())
+                .map(|f| typestate_param(f.field_ident()))
+                .collect(),
+            fields: self
+                .raw_fields()
+                .into_iter()
+                .map(|f| {
+                    let cfg_attrs = f
+                        .attrs
+                        .field
+                        .iter()
+                        .filter(|attr| attr.path().is_ident("cfg"))
+                        .collect();
+                    (
+                        f.ident
+                            .clone()
+                            .expect("Fields are named in Options::resolve"),
+                        cfg_attrs,
+                    )
+                })
+                .chain(
+                    self.as_variant_phantom_field()
+                        .map(|_| (VariantPhantomField::ident(), vec![])),
+                )
+                .chain(
+                    self.as_fn_phantom_field()
+                        .map(|_| (FnPhantomField::ident(), vec![])),
+                )
+                .collect(),
+            build_fn: Default::default(),
+        })
+    }
+
+    /// Returns the conversion of a target value into the builder, if it is enabled.
+    pub fn as_from_target(&self) -> Option<FromTarget<'_>> {
+        if !self.converts_from_target() || self.is_enum() {
+            return None;
+        }
+
+        let typestate = self.as_typestate();
+        let builder_ty = match &typestate {
+            Some(typestate) => typestate.set_ty(),
+            None => {
+                let builder_ident = self.builder_ident();
+                let (_, ty_generics, _) = self.generics.split_for_impl();
+                quote!(#builder_ident #ty_generics)
+            }
+        };
+
+        Some(FromTarget {
+            crate_root: &self.crate_root,
+            target_ty: &self.ident,
+            generics: &self.generics,
+            builder_ty,
+            field_initializers: self
+                .fields()
+                .map(|f| f.target_value_initializer())
+                .chain(typestate.as_ref().map(Typestate::field_initializer))
+                .chain(
+                    self.as_try_setter_errors_field()
+                        .as_ref()
+                        .map(TrySetterErrorsField::initializer),
+                )
+                .collect(),
+            to_builder: self.to_builder.is_present().then(|| self.builder_vis()),
+        })
+    }
+
+    /// Returns the method which clears all fields of the builder.
+    fn as_reset(&self) -> Reset<'_> {
+        let typestate = self
+            .as_typestate()
+            .filter(|typestate| !typestate.params.is_empty());
+
+        Reset {
+            crate_root: &self.crate_root,
+            enabled: self.setter.clear.unwrap_or_default(),
+            visibility: self
+                .visibility
+                .to_explicit_visibility()
+                .unwrap_or_else(|| Cow::Owned(parse_quote!(pub))),
+            pattern: self.pattern(),
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled() && f.field.setter.clear != Some(false))
+                .map(|f| {
+                    let cfg_attrs = f
+                        .field
+                        .attrs
+                        .field
+                        .iter()
+                        .filter(|attr| attr.path().is_ident("cfg"))
+                        .collect();
+                    (f.field_ident().clone(), cfg_attrs)
+                })
+                .chain(
+                    self.as_try_setter_errors_field()
+                        .map(|_| (TrySetterErrorsField::ident(), vec![])),
+                )
+                .collect(),
+            typestate: typestate.map(|typestate| typestate.unset_ty()),
+        }
+    }
+
+    /// Returns the implementation of `IntoFuture` of the builder, if it is generated for an
+    /// `async` function.
+    fn as_into_future(&self) -> Option<IntoFuture<'_>> {
+        let target_fn = self.target_fn.as_ref().filter(|f| f.is_async)?;
+        if self.build_fn.skip {
+            return None;
+        }
+
+        let crate_root = &self.crate_root;
+        let fn_output = &target_fn.output;
+        let output = if self.infallible() {
+            quote!(#fn_output)
+        } else {
+            let error_ty = self.builder_error_ident();
+            quote!(#crate_root::export::core::result::Result<#fn_output, #error_ty>)
+        };
+
+        Some(IntoFuture {
+            crate_root,
+            build_fn: &self.build_fn.name,
+            output,
+            lifetime: target_fn
+                .receiver_lifetime
+                .clone()
+                .unwrap_or_else(|| parse_quote!('static)),
+        })
+    }
+
+    /// Returns the field which uses the generic parameters of the enum in a variant builder,
+    /// if it is needed.
+    fn as_variant_phantom_field(&self) -> Option<VariantPhantomField<'_>> {
+        if self.variant.is_none() || self.generics.params.is_empty() {
+            return None;
+        }
+
+        Some(VariantPhantomField {
+            crate_root: &self.crate_root,
+            target_ty: &self.ident,
+            generics: &self.generics,
+        })
+    }
+
+    /// Returns the field which uses the generic parameters of the function in the builder of
+    /// a function, if it is needed.
+    fn as_fn_phantom_field(&self) -> Option<FnPhantomField<'_>> {
+        if self.target_fn.is_none() || self.generics.params.is_empty() {
+            return None;
+        }
+
+        Some(FnPhantomField {
+            crate_root: &self.crate_root,
+            generics: &self.generics,
+        })
+    }
+
+    /// Returns the functions of the builder of an enum which create the variant builders.
+    fn as_variant_builder_fns(&self) -> Vec<proc_macro2::TokenStream> {
+        self.variants()
+            .iter()
+            .map(|options| {
+                let variant = options
+                    .variant
+                    .as_ref()
+                    .expect("Options::variants sets the variant");
+                let impl_generics = options.as_builder().compute_impl_bounds();
+                VariantBuilderFn {
+                    ident: variant_fn_ident(&variant.ident),
+                    visibility: options.builder_vis(),
+                    builder_ident: options.builder_ident(),
+                    generics: Some(&impl_generics),
+                    create_empty: &self.create_empty,
+                    doc_comment: Some(doc_comment_from(format!(
+                        include_str!("doc_tpl/builder_variant_fn.md"),
+                        struct_name = self.ident,
+                        variant_name = variant.ident
+                    ))),
+                }
+                .into_token_stream()
+            })
+            .collect()
+    }
+
+    /// Doc-comment of the build method, which depends on the ways it can fail.
+    fn build_method_doc(&self) -> String {
+        let name = self.target_name();
+        match (
+            self.target_fn.is_some(),
+            self.typestate.is_present(),
+            self.build_fn.infallible.is_present(),
+        ) {
+            (false, true, _) => format!(
+                include_str!("doc_tpl/builder_method_typestate.md"),
+                struct_name = name
+            ),
+            (false, false, true) => format!(
+                include_str!("doc_tpl/builder_method_infallible.md"),
+                struct_name = name
+            ),
+            (false, false, false) => {
+                format!(
+                    include_str!("doc_tpl/builder_method.md"),
+                    struct_name = name
+                )
+            }
+            (true, true, _) => format!(
+                include_str!("doc_tpl/builder_method_fn_typestate.md"),
+                fn_name = name
+            ),
+            (true, false, true) => format!(
+                include_str!("doc_tpl/builder_method_fn_infallible.md"),
+                fn_name = name
+            ),
+            (true, false, false) => {
+                format!(include_str!("doc_tpl/builder_method_fn.md"), fn_name = name)
+            }
         }
     }
 
@@ -803,20 +2138,63 @@ impl Options {
             enabled: !self.build_fn.skip,
             ident: &self.build_fn.name,
             visibility: self.build_method_vis(),
-            pattern: self.pattern,
+            pattern: self.pattern(),
             target_ty: &self.ident,
+            target_variant: self.variant.as_ref().map(|v| &v.ident),
+            target_fn: self.target_fn.as_ref(),
             target_ty_generics: Some(ty_generics),
             error_ty: self.builder_error_ident(),
+            infallible: self.infallible(),
             initializers: self
                 .fields()
+                .filter(|f| f.field.computed.is_none())
                 .map(|f| f.as_initializer().into_token_stream())
                 .collect(),
-            doc_comment: Some(doc_comment_from(format!(
-                include_str!("doc_tpl/builder_method.md"),
-                struct_name = self.ident
-            ))),
+            bound_fields: if self.has_computed_fields() {
+                self.fields()
+                    .filter(|f| f.field.computed.is_none())
+                    .map(|f| (f.target_member(), f.field_ident()))
+                    .collect()
+            } else {
+                vec![]
+            },
+            computed_fields: self
+                .fields()
+                .filter_map(|f| f.as_computed_field())
+                .collect(),
+            doc_comment: Some(doc_comment_from(self.build_method_doc())),
             default_struct: self.default.as_ref(),
             validate_fn: self.build_fn.validate.as_ref(),
+            validate_target_fn: self.build_fn.validate_target.as_ref(),
+            collect_validation_errors: self.build_fn.collect_validation_errors.is_present(),
+            try_setter_errors: self.as_try_setter_errors_field().is_some(),
+            collected_fields: self
+                .fields()
+                .filter(|f| f.collect_uninitialized())
+                .map(|f| f.field_ident())
+                .collect(),
+            group_checks: self
+                .groups
+                .iter()
+                .map(|group| {
+                    let (rule, fields) = group.rule();
+                    GroupCheck {
+                        crate_root: &self.crate_root,
+                        name: &group.name,
+                        rule,
+                        fields: fields
+                            .iter()
+                            .filter_map(|path| self.group_field(path))
+                            .map(|field| field.field_ident())
+                            .collect(),
+                        custom_error_type_span: self.custom_error_type_span(),
+                        collect_validation_errors: self
+                            .build_fn
+                            .collect_validation_errors
+                            .is_present(),
+                    }
+                })
+                .collect(),
         }
     }
 }
@@ -833,25 +2211,61 @@ pub struct FieldWithDefaults<'a> {
 impl<'a> FieldWithDefaults<'a> {
     /// Check if this field should emit a setter.
     pub fn setter_enabled(&self) -> bool {
-        self.field
-            .setter
-            .setter_enabled()
-            .or_else(|| self.parent.setter.enabled())
-            .unwrap_or(true)
+        self.field.computed.is_none()
+            && self
+                .field
+                .setter
+                .setter_enabled()
+                .or_else(|| self.parent.setter.enabled())
+                .unwrap_or(true)
     }
 
     pub fn field_enabled(&self) -> bool {
-        self.field
-            .setter
-            .field_enabled()
-            .or_else(|| self.parent.setter.enabled())
-            .unwrap_or(true)
+        self.field.computed.is_none()
+            && self
+                .field
+                .setter
+                .field_enabled()
+                .or_else(|| self.parent.setter.enabled())
+                .unwrap_or(true)
     }
 
     /// Check if this field should emit a fallible setter.
     /// This depends on the `TryFrom` trait, which hasn't yet stabilized.
     pub fn try_setter(&self) -> bool {
-        self.field.try_setter.is_present() || self.parent.try_setter.is_present()
+        self.field.try_setter.is_some() || self.parent.try_setter.is_some()
+    }
+
+    /// Check if the fallible setter of this field records its errors in the builder.
+    pub fn defer_try_setter_errors(&self) -> bool {
+        self.try_setter()
+            && [&self.field.try_setter, &self.parent.try_setter]
+                .iter()
+                .any(|t| t.as_ref().map_or(false, |t| t.defer.is_present()))
+    }
+
+    /// Check if this field should emit a method which clears it.
+    ///
+    /// Required fields of typestate builders cannot be cleared, as that would change the
+    /// builder's typestate.
+    pub fn clear_enabled(&self) -> bool {
+        self.field_enabled()
+            && !self.typestate_required()
+            && self
+                .field
+                .setter
+                .clear
+                .or(self.parent.setter.clear)
+                .unwrap_or_default()
+    }
+
+    /// Check if this field should emit a getter.
+    pub fn getter_enabled(&self) -> bool {
+        self.field_enabled()
+            && self
+                .field
+                .getter
+                .unwrap_or_else(|| self.parent.getter.is_present())
     }
 
     /// Get the prefix that should be applied to the field name to produce
@@ -864,6 +2278,37 @@ impl<'a> FieldWithDefaults<'a> {
             .or(self.parent.setter.prefix.as_ref())
     }
 
+    /// Get the setters forwarded to the builder of a `flatten` field, named with the setter
+    /// prefix and the prefix of `flatten`.
+    pub fn flattened_setters(&self) -> Vec<FlattenedSetter<'_>> {
+        let flatten = match &self.field.flatten {
+            Some(flatten) => flatten,
+            None => return vec![],
+        };
+
+        let prefix: String = self
+            .setter_prefix()
+            .into_iter()
+            .chain(&flatten.prefix)
+            .map(|prefix| format!("{}_", prefix))
+            .collect();
+
+        flatten
+            .setters
+            .0
+            .iter()
+            .map(|(setter, ty)| FlattenedSetter {
+                ident: if prefix.is_empty() {
+                    setter.clone()
+                } else {
+                    format_ident!("{}{}", prefix, setter)
+                },
+                setter,
+                ty,
+            })
+            .collect()
+    }
+
     /// Get the ident of the emitted setter method
     pub fn setter_ident(&self) -> syn::Ident {
         if let Some(ref custom) = self.field.setter.name {
@@ -879,6 +2324,15 @@ impl<'a> FieldWithDefaults<'a> {
         ident.clone().unwrap()
     }
 
+    /// Name of the setter without the `prefix`, which only applies to setters, e.g. for
+    /// the getter `get_foo()` and the method `clear_foo()` of a setter `with_foo()`.
+    pub fn unprefixed_setter_ident(&self) -> syn::Ident {
+        match self.field.setter.name {
+            Some(ref custom) => custom.clone(),
+            None => self.field.ident.clone().unwrap(),
+        }
+    }
+
     /// Checks if the emitted setter should be generic over types that impl
     /// `Into<FieldType>`.
     pub fn setter_into(&self) -> bool {
@@ -889,14 +2343,63 @@ impl<'a> FieldWithDefaults<'a> {
             .unwrap_or_default()
     }
 
+    /// Get the `each` setter of this field, if it has validators.
+    fn each_with_validators(&self) -> Option<&Each> {
+        self.field
+            .setter
+            .each
+            .as_ref()
+            .filter(|each| each.has_validators())
+    }
+
     /// Checks if the emitted setter should strip the wrapper Option over types that impl
     /// `Option<FieldType>`.
     pub fn setter_strip_option(&self) -> bool {
         self.field
             .setter
             .strip_option
-            .or(self.parent.setter.strip_option)
-            .unwrap_or_default()
+            .as_ref()
+            .or(self.parent.setter.strip_option.as_ref())
+            .map_or(false, |strip_option| strip_option.enabled)
+    }
+
+    /// Get the type wrapped by the `Option` of this field, if it was given explicitly.
+    pub fn setter_strip_option_inner(&self) -> Option<&syn::Type> {
+        self.field
+            .setter
+            .strip_option
+            .as_ref()
+            .and_then(|strip_option| strip_option.inner.as_ref())
+    }
+
+    /// Get the name of the setter which takes an `Option`, if the setter strips the option
+    /// and a fallback is requested for this field or at the struct level.
+    pub fn setter_strip_option_fallback(&self) -> Option<Ident> {
+        if !self.setter_strip_option() {
+            return None;
+        }
+
+        let fallback = self
+            .field
+            .setter
+            .strip_option
+            .as_ref()
+            .and_then(|strip_option| strip_option.fallback.as_ref())
+            .or_else(|| {
+                self.parent
+                    .setter
+                    .strip_option
+                    .as_ref()
+                    .and_then(|strip_option| strip_option.fallback.as_ref())
+            })?;
+
+        Some(match fallback {
+            Override::Explicit(name) => name.clone(),
+            Override::Inherit => {
+                let setter_ident = self.setter_ident().unraw().to_string();
+                format_ident!("maybe_{}", setter_ident.trim_start_matches('_'))
+            }
+        })
     }
 
     /// Get the visibility of the emitted setter, if there will be one.
@@ -910,11 +2413,21 @@ impl<'a> FieldWithDefaults<'a> {
 
     /// Get the ident of the input field. This is also used as the ident of the
     /// emitted field.
-    pub fn field_ident(&self) -> &syn::Ident {
+    ///
+    /// Fields of tuple structs are named `_0`, `_1`, etc.
+    pub fn field_ident(&self) -> &'a syn::Ident {
         self.field
             .ident
             .as_ref()
-            .expect("Tuple structs are not supported")
+            .expect("Fields are named in Options::resolve")
+    }
+
+    /// The target field, by name or by index if it belongs to a tuple struct.
+    pub fn target_member(&self) -> syn::Member {
+        match &self.field.index {
+            Some(index) => syn::Member::Unnamed(index.clone()),
+            None => syn::Member::Named(self.field_ident().clone()),
+        }
     }
 
     pub fn field_vis(&self) -> Cow<'_, syn::Visibility> {
@@ -957,12 +2470,51 @@ impl<'a> FieldWithDefaults<'a> {
     }
 
     pub fn pattern(&self) -> BuilderPattern {
-        self.field.pattern.unwrap_or(self.parent.pattern)
+        self.field.pattern.unwrap_or_else(|| self.parent.pattern())
     }
 
     pub fn use_parent_default(&self) -> bool {
         self.field.default.is_none() && self.parent.default.is_some()
     }
+
+    /// Check if the build method fails when this field has not been set.
+    pub fn is_required(&self) -> bool {
+        self.field_enabled()
+            && matches!(self.conversion(), FieldConversion::OptionOrDefault)
+            && self.field.default.is_none()
+            && self.parent.default.is_none()
+    }
+
+    /// Check if this field must be set before the build method of a typestate builder
+    /// becomes available.
+    pub fn typestate_required(&self) -> bool {
+        self.parent.typestate.is_present() && self.is_required()
+    }
+
+    /// Check if this field is checked up front by a build method which reports all
+    /// uninitialized fields at once.
+    pub fn collect_uninitialized(&self) -> bool {
+        self.parent.build_fn.collect_uninitialized.is_present()
+            && !self.parent.typestate.is_present()
+            && self.is_required()
+    }
+
+    /// Get the span of the custom error type of the build method, if there is one.
+    fn custom_error_type_span(&self) -> Option<Span> {
+        self.parent.custom_error_type_span()
+    }
+
+    /// Get the builder type returned by this field's setters, if setting the field changes
+    /// the builder's typestate.
+    fn typestate_transition_ty(&self) -> Option<proc_macro2::TokenStream> {
+        if !self.typestate_required() {
+            return None;
+        }
+
+        self.parent
+            .as_typestate()
+            .map(|typestate| typestate.transition_ty(&typestate_param(self.field_ident())))
+    }
 }
 
 /// Converters to codegen structs
@@ -973,6 +2525,7 @@ impl<'a> FieldWithDefaults<'a> {
             crate_root: &self.parent.crate_root,
             setter_enabled: self.setter_enabled(),
             try_setter: self.try_setter(),
+            defer_try_setter_errors: self.defer_try_setter_errors(),
             visibility: self.setter_vis(),
             pattern: self.pattern(),
             attrs: &self.field.attrs.setter,
@@ -981,7 +2534,48 @@ impl<'a> FieldWithDefaults<'a> {
             field_type: self.field_type(),
             generic_into: self.setter_into(),
             strip_option: self.setter_strip_option(),
+            strip_option_inner: self.setter_strip_option_inner(),
+            strip_option_fallback: self.setter_strip_option_fallback(),
             each: self.field.setter.each.as_ref(),
+            transform: self.field.setter.transform.as_ref(),
+            typestate: self.typestate_transition_ty(),
+            sub_builder: self.field.sub_builder.is_present(),
+            flattened: self.flattened_setters(),
+        }
+    }
+
+    /// Returns a `Clear` according to the options.
+    pub fn as_clear(&'a self) -> Clear<'a> {
+        Clear {
+            crate_root: &self.parent.crate_root,
+            clear_enabled: self.clear_enabled(),
+            visibility: self.setter_vis(),
+            pattern: self.pattern(),
+            field_ident: self.field_ident(),
+            setter_ident: self.unprefixed_setter_ident(),
+            discard_try_setter_errors: self.defer_try_setter_errors(),
+        }
+    }
+
+    /// Returns a `ComputedField` if the field is computed by the build method.
+    pub fn as_computed_field(&self) -> Option<ComputedField<'a>> {
+        self.field.computed.as_ref().map(|expr| ComputedField {
+            member: self.target_member(),
+            ident: self.field_ident(),
+            ty: &self.field.ty,
+            expr,
+        })
+    }
+
+    /// Returns a `Getter` according to the options.
+    pub fn as_getter(&'a self) -> Getter<'a> {
+        Getter {
+            crate_root: &self.parent.crate_root,
+            getter_enabled: self.getter_enabled(),
+            visibility: self.setter_vis(),
+            field_ident: self.field_ident(),
+            setter_ident: self.unprefixed_setter_ident(),
+            field_type: self.field_type(),
         }
     }
 
@@ -991,19 +2585,54 @@ impl<'a> FieldWithDefaults<'a> {
             crate_root: &self.parent.crate_root,
             field_enabled: self.field_enabled(),
             field_ident: self.field_ident(),
+            field_index: self.field.index.as_ref(),
+            fn_argument: self.parent.target_fn.is_some() || self.parent.has_computed_fields(),
             builder_pattern: self.pattern(),
             default_value: self.field.default.as_ref(),
             use_default_struct: self.use_parent_default(),
             conversion: self.conversion(),
-            custom_error_type_span: self.parent.build_fn.error.as_ref().and_then(|err_ty| {
-                match err_ty {
-                    BuildFnError::Existing(p) => Some(p.span()),
-                    _ => None,
-                }
+            custom_error_type_span: self.custom_error_type_span(),
+            known_initialized: self.typestate_required() || self.collect_uninitialized(),
+            validation: self.field.has_validators().then(|| FieldValidation {
+                crate_root: &self.parent.crate_root,
+                field_name: self.field_ident().unraw().to_string(),
+                validators: self.field.validate.as_ref(),
+                each: self.each_with_validators(),
+                each_in_option: self.setter_strip_option()
+                    && (self.setter_strip_option_inner().is_some()
+                        || extract_type_from_option(&self.field.ty).is_some()),
+                custom_error_type_span: self.custom_error_type_span(),
+                collect: self.parent.build_fn.collect_validation_errors.is_present(),
             }),
         }
     }
 
+    /// Returns the initializer of this field's builder field from the target struct `value`,
+    /// used by `FromTarget`.
+    pub fn target_value_initializer(&self) -> proc_macro2::TokenStream {
+        let crate_root = &self.parent.crate_root;
+        let ident = self.field_ident();
+        let cfg_attrs = self
+            .field
+            .attrs
+            .field
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"));
+        let member = self.target_member();
+
+        let value = if !self.field_enabled() {
+            quote!(#crate_root::export::core::default::Default::default())
+        } else if let Some(from_target) = &self.field.field.from_target {
+            quote!((#from_target)(value.#member))
+        } else if self.field.sub_builder.is_present() {
+            quote!(#crate_root::export::core::convert::From::from(value.#member))
+        } else {
+            quote!(#crate_root::export::core::option::Option::Some(value.#member))
+        };
+
+        quote!(#(#cfg_attrs)* #ident: #value,)
+    }
+
     pub fn as_builder_field(&'a self) -> BuilderField<'a> {
         BuilderField {
             crate_root: &self.parent.crate_root,
@@ -39,10 +39,236 @@ pub struct Each {
     pub name: syn::Ident,
     #[darling(default)]
     pub into: bool,
+    /// Whether the setter takes a key and a value, which are converted with `Into` and
+    /// added to the collection as a tuple.
+    #[darling(default)]
+    pub key_value: bool,
+    /// Minimum number of items in the collection when it is built.
+    pub min: Option<syn::Expr>,
+    /// Maximum number of items in the collection when it is built.
+    pub max: Option<syn::Expr>,
+    /// Function which takes each item of the collection, as yielded when iterating over a
+    /// reference to it, and returns `Result<(), String>`.
+    pub validate: Option<syn::Path>,
+    /// Require the items of the collection to be distinct, compared with `PartialEq`.
+    ///
+    /// As items need neither `Hash` nor `Ord`, each item is compared with every earlier one,
+    /// which is quadratic in the number of items. It therefore requires `max`, and is not
+    /// checked if there are more items.
+    pub unique: darling::util::Flag,
+}
+
+impl Each {
+    /// Returns whether the collection is checked when it is built.
+    pub fn has_validators(&self) -> bool {
+        self.min.is_some()
+            || self.max.is_some()
+            || self.validate.is_some()
+            || self.unique.is_present()
+    }
 }
 
 impl From<syn::Ident> for Each {
     fn from(name: syn::Ident) -> Self {
-        Self { name, into: false }
+        Self {
+            name,
+            into: false,
+            key_value: false,
+            min: None,
+            max: None,
+            validate: None,
+            unique: Default::default(),
+        }
+    }
+}
+
+/// Closure which computes the value of a field from the arguments of its setter, from
+/// `#[builder(setter(transform = |host: &str, port: u16| ...))]`.
+///
+/// The parameters of the closure become the parameters of the setter, so each of them needs
+/// a name and a type.
+#[derive(Debug, Clone)]
+pub struct SetterTransform {
+    pub closure: syn::ExprClosure,
+}
+
+impl SetterTransform {
+    /// Returns the parameters of the closure, with their names.
+    pub fn params(&self) -> impl Iterator<Item = (&syn::PatType, &syn::Ident)> {
+        self.closure.inputs.iter().map(|input| match input {
+            syn::Pat::Type(param) => match &*param.pat {
+                syn::Pat::Ident(pat) => (param, &pat.ident),
+                _ => unreachable!("checked when parsing"),
+            },
+            _ => unreachable!("checked when parsing"),
+        })
+    }
+
+    fn from_closure(closure: syn::ExprClosure) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+        for input in &closure.inputs {
+            let named = match input {
+                syn::Pat::Type(param) => {
+                    matches!(&*param.pat, syn::Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none())
+                }
+                _ => false,
+            };
+            if !named {
+                errors.push(
+                    darling::Error::custom(
+                        "parameters of `transform` need a name and a type, e.g. `port: u16`",
+                    )
+                    .with_span(input),
+                );
+            }
+        }
+
+        errors.finish_with(Self { closure })
+    }
+}
+
+impl darling::FromMeta for SetterTransform {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Closure(closure) => Self::from_closure(closure.clone()),
+            syn::Expr::Lit(lit) => Self::from_value(&lit.lit),
+            syn::Expr::Group(group) => Self::from_expr(&group.expr),
+            _ => Err(darling::Error::custom("expected a closure").with_span(expr)),
+        }
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        syn::parse_str(value)
+            .map_err(|_| darling::Error::custom("expected a closure"))
+            .and_then(Self::from_closure)
+    }
+}
+
+/// Options of a field whose builder's setters are forwarded by the parent builder, from
+/// `#[builder(flatten(prefix = "retry", setters(attempts = "u32")))]`.
+///
+/// The macro cannot see the fields of another struct, so the forwarded setters are listed
+/// with the type of their value.
+#[derive(Debug, Clone, FromMeta)]
+pub struct Flatten {
+    /// Prefix of the forwarded setters, e.g. `retry` for `retry_attempts`.
+    pub prefix: Option<syn::Ident>,
+    /// Setters of the field's builder, with the type of their value.
+    pub setters: ForwardedSetters,
+}
+
+/// Setters listed as `name = "Type"` in `flatten(setters(...))`.
+#[derive(Debug, Clone)]
+pub struct ForwardedSetters(pub Vec<(syn::Ident, syn::Type)>);
+
+impl darling::FromMeta for ForwardedSetters {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+        let setters = items
+            .iter()
+            .filter_map(|item| {
+                errors.handle_in(|| match item {
+                    darling::ast::NestedMeta::Meta(syn::Meta::NameValue(nv)) => {
+                        let name = nv.path.get_ident().ok_or_else(|| {
+                            darling::Error::custom("expected the name of a setter")
+                                .with_span(&nv.path)
+                        })?;
+                        let ty =
+                            syn::Type::from_expr(&nv.value).map_err(|e| e.with_span(&nv.value))?;
+                        Ok((name.clone(), ty))
+                    }
+                    _ => Err(darling::Error::custom(
+                        r#"expected a setter and the type of its value, e.g. `attempts = "u32"`"#,
+                    )
+                    .with_span(item)),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if items.is_empty() {
+            errors.push(darling::Error::custom("expected at least one setter"));
+        }
+
+        errors.finish_with(ForwardedSetters(setters))
+    }
+}
+
+/// Validators of the final value of a field, from `#[builder(validate(...))]`.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct FieldValidators {
+    /// Bounds of the value, e.g. `range(min = 1, max = 65535)`.
+    pub range: Option<Bounds>,
+    /// Require the value to not be empty, checked with its `is_empty` method.
+    pub non_empty: darling::util::Flag,
+    /// Bounds of the length of the value, checked with its `len` method.
+    pub len: Option<Bounds>,
+    /// Function which takes a reference to the value and returns `Result<(), String>`.
+    pub with: Option<syn::Path>,
+}
+
+/// Inclusive bounds of a `range` or `len` validator.
+#[derive(Debug, Clone, FromMeta)]
+#[darling(and_then = Self::check_not_empty)]
+pub struct Bounds {
+    pub min: Option<syn::Expr>,
+    pub max: Option<syn::Expr>,
+}
+
+impl Bounds {
+    fn check_not_empty(self) -> darling::Result<Self> {
+        if self.min.is_none() && self.max.is_none() {
+            return Err(darling::Error::custom("expected `min`, `max` or both"));
+        }
+
+        Ok(self)
+    }
+}
+
+/// Group of fields whose combination of set fields is checked by the build method, from
+/// `#[builder(group(...))]`.
+#[derive(Debug, Clone, FromMeta)]
+#[darling(and_then = Self::check_rule)]
+pub struct FieldGroup {
+    /// Name of the group, which is reported in the error.
+    pub name: String,
+    /// Fields of which exactly one must be set.
+    pub exactly_one: Option<darling::util::PathList>,
+    /// Fields of which at least one must be set.
+    pub at_least_one: Option<darling::util::PathList>,
+    /// A field, followed by the fields which must be set if it is set.
+    pub requires: Option<darling::util::PathList>,
+}
+
+impl FieldGroup {
+    /// Returns the name of the `GroupRule` variant of the group, and its fields.
+    pub fn rule(&self) -> (&'static str, &[syn::Path]) {
+        match (&self.exactly_one, &self.at_least_one, &self.requires) {
+            (Some(fields), _, _) => ("ExactlyOne", fields),
+            (_, Some(fields), _) => ("AtLeastOne", fields),
+            (_, _, Some(fields)) => ("Requires", fields),
+            (None, None, None) => unreachable!("checked when parsing"),
+        }
+    }
+
+    fn check_rule(self) -> darling::Result<Self> {
+        let rules = [&self.exactly_one, &self.at_least_one, &self.requires];
+        if rules.iter().filter(|rule| rule.is_some()).count() != 1 {
+            return Err(darling::Error::custom(
+                "expected exactly one of `exactly_one`, `at_least_one` or `requires`",
+            ));
+        }
+
+        let mut errors = darling::Error::accumulator();
+        let (_, fields) = self.rule();
+        if fields.len() < 2 {
+            errors.push(darling::Error::custom("expected at least two fields"));
+        }
+        for field in fields {
+            if field.get_ident().is_none() {
+                errors.push(darling::Error::custom("expected a field name").with_span(field));
+            }
+        }
+
+        errors.finish_with(self)
     }
 }
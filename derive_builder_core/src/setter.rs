@@ -2,9 +2,15 @@
 use std::borrow::Cow;
 
 use proc_macro2::{Span, TokenStream};
-use quote::{ToTokens, TokenStreamExt};
+use quote::{format_ident, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
 
-use crate::{BuilderFieldType, BuilderPattern, Each};
+use crate::typestate::TYPESTATE_TRANSITION_NAME;
+use crate::{BuilderFieldType, BuilderPattern, Each, SetterTransform};
+
+/// Name of the builder field in which fallible setters record their errors, if they are
+/// deferred to the build method.
+const TRY_SETTER_ERRORS_NAME: &str = "__try_setter_errors";
 
 /// Setter for the struct fields in the build method, implementing
 /// `quote::ToTokens`.
@@ -43,6 +49,9 @@ pub struct Setter<'a> {
     pub setter_enabled: bool,
     /// Enables code generation for the `try_` variant of this setter fn.
     pub try_setter: bool,
+    /// Make the `try_` variant of this setter record a failed conversion in the builder and
+    /// return the builder, instead of returning a `Result`.
+    pub defer_try_setter_errors: bool,
     /// Visibility of the setter, e.g. `syn::Visibility::Public`.
     pub visibility: Cow<'a, syn::Visibility>,
     /// How the setter method takes and returns `self` (e.g. mutably).
@@ -62,8 +71,40 @@ pub struct Setter<'a> {
     /// Make the setter remove the Option wrapper from the setter, remove the need to call Some(...).
     /// when combined with into, the into is used on the content Type of the Option.
     pub strip_option: bool,
+    /// The type wrapped by the `Option` if the field type is not recognized as one, e.g.
+    /// because it is an alias.
+    pub strip_option_inner: Option<&'a syn::Type>,
+    /// Emit an additional setter with this name, which takes an `Option` if the setter strips
+    /// the option.
+    pub strip_option_fallback: Option<syn::Ident>,
     /// Emit extend method.
     pub each: Option<&'a Each>,
+    /// Closure which computes the value from the arguments of the setter, whose parameters
+    /// are those of the closure.
+    pub transform: Option<&'a SetterTransform>,
+    /// Builder type to return for a required field of a typestate builder.
+    ///
+    /// Setting the field changes the builder's typestate, so the setter returns a different
+    /// type than `Self`. This requires the owned pattern.
+    pub typestate: Option<TokenStream>,
+    /// The builder field holds the builder of the target field.
+    ///
+    /// Instead of a setter, emit a `_mut` accessor to the field's builder and a `_with`
+    /// method which takes a closure to configure it.
+    pub sub_builder: bool,
+    /// Setters of the builder of a `sub_builder` field, which this builder forwards.
+    pub flattened: Vec<FlattenedSetter<'a>>,
+}
+
+/// Setter of the builder of a `flatten` field, which the parent builder forwards.
+#[derive(Debug, Clone)]
+pub struct FlattenedSetter<'a> {
+    /// Name of the forwarding setter.
+    pub ident: syn::Ident,
+    /// Name of the setter of the field's builder.
+    pub setter: &'a syn::Ident,
+    /// Type of the value taken by the setter.
+    pub ty: &'a syn::Type,
 }
 
 impl<'a> ToTokens for Setter<'a> {
@@ -77,8 +118,9 @@ impl<'a> ToTokens for Setter<'a> {
             let attrs = self.attrs;
 
             let self_param: TokenStream;
-            let return_ty: TokenStream;
+            let mut return_ty: TokenStream;
             let self_into_return_ty: TokenStream;
+            let mut return_new = quote!(new);
 
             match pattern {
                 BuilderPattern::Owned => {
@@ -99,6 +141,75 @@ impl<'a> ToTokens for Setter<'a> {
                 }
             };
 
+            if let Some(ref typestate) = self.typestate {
+                let transition = syn::Ident::new(TYPESTATE_TRANSITION_NAME, Span::call_site());
+                return_ty = typestate.clone();
+                return_new = quote!(new.#transition());
+            }
+
+            if self.sub_builder {
+                let (ty, _) = self.field_type.setter_type_info();
+                let ident_mut = format_ident!("{}_mut", ident);
+                let ident_with = format_ident!("{}_with", ident);
+
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #vis fn #ident_mut(&mut self) -> &mut #ty {
+                        &mut self.#field_ident
+                    }
+
+                    #(#attrs)*
+                    #[allow(unused_mut)]
+                    #vis fn #ident_with<BUILD: #crate_root::export::core::ops::FnOnce(&mut #ty)>(#self_param, build: BUILD)
+                        -> #return_ty
+                    {
+                        let mut new = #self_into_return_ty;
+                        build(&mut new.#field_ident);
+                        #return_new
+                    }
+                ));
+
+                // The forwarded setters must return a mutable reference to the field's
+                // builder, so that a builder using another pattern fails to compile instead
+                // of discarding the value.
+                for flattened in &self.flattened {
+                    let forward_ident = &flattened.ident;
+                    let setter = flattened.setter;
+                    let value_ty = flattened.ty;
+                    let (ty_params, param_ty, value) = if self.generic_into {
+                        (
+                            quote!(<VALUE: #crate_root::export::core::convert::Into<#value_ty>>),
+                            quote!(VALUE),
+                            quote!(value.into()),
+                        )
+                    } else {
+                        (quote!(), quote!(#value_ty), quote!(value))
+                    };
+                    let forward = quote_spanned!(setter.span()=>
+                        let _: &mut #ty = new.#field_ident.#setter(#value);
+                    );
+                    let doc = format!(
+                        "Calls `{}` on the builder of `{}`.",
+                        setter.unraw(),
+                        field_ident.unraw()
+                    );
+
+                    tokens.append_all(quote!(
+                        #[doc = #doc]
+                        #(#attrs)*
+                        #[allow(unused_mut)]
+                        #vis fn #forward_ident #ty_params (#self_param, value: #param_ty)
+                            -> #return_ty
+                        {
+                            let mut new = #self_into_return_ty;
+                            #forward
+                            #return_new
+                        }
+                    ));
+                }
+                return;
+            }
+
             let ty_params: TokenStream;
             let param_ty: TokenStream;
             let mut into_value: TokenStream;
@@ -107,7 +218,10 @@ impl<'a> ToTokens for Setter<'a> {
 
             let (ty, stripped_option) = {
                 if self.strip_option {
-                    match extract_type_from_option(field_type) {
+                    match self
+                        .strip_option_inner
+                        .or_else(|| extract_type_from_option(field_type))
+                    {
                         Some(ty) => (ty, true),
                         None => (field_type, false),
                     }
@@ -116,7 +230,8 @@ impl<'a> ToTokens for Setter<'a> {
                 }
             };
 
-            if self.generic_into {
+            // The value computed by a transform already has the field type
+            if self.generic_into && self.transform.is_none() {
                 ty_params = quote!(<VALUE: #crate_root::export::core::convert::Into<#ty>>);
                 param_ty = quote!(VALUE);
                 into_value = quote!(value.into());
@@ -134,17 +249,51 @@ impl<'a> ToTokens for Setter<'a> {
                 into_value = wrap_expression_in_some(crate_root, into_value);
             }
 
-            tokens.append_all(quote!(
-                #(#attrs)*
-                #[allow(unused_mut)]
-                #vis fn #ident #ty_params (#self_param, value: #param_ty)
-                    -> #return_ty
-                {
-                    let mut new = #self_into_return_ty;
-                    new.#field_ident = #into_value;
-                    new
-                }
-            ));
+            // Unlike method names, the field name in errors keeps its leading underscores.
+            let field_name = field_ident.unraw().to_string();
+            let try_setter_errors = TrySetterErrorsField::ident();
+            let record_error = quote!(
+                new.#try_setter_errors.push(#crate_root::TrySetterError::new(
+                    #field_name,
+                    #crate_root::export::core::string::ToString::to_string(&error)
+                ))
+            );
+            // Setting the field replaces the value which failed to convert, so its errors are
+            // discarded. Adding items to a collection doesn't replace the failed items.
+            let discard_errors = self.defer_try_setter_errors.then(|| {
+                quote!(
+                    new.#try_setter_errors.retain(|error| error.field_name() != #field_name);
+                )
+            });
+
+            if let Some(transform) = self.transform {
+                let closure = &transform.closure;
+                let (params, args): (Vec<_>, Vec<_>) = transform.params().unzip();
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #[allow(unused_mut)]
+                    #vis fn #ident(#self_param, #(#params),*) -> #return_ty {
+                        let value: #ty = (#closure)(#(#args),*);
+                        let mut new = #self_into_return_ty;
+                        new.#field_ident = #into_value;
+                        #discard_errors
+                        #return_new
+                    }
+                ));
+            } else {
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #[allow(unused_mut)]
+                    #vis fn #ident #ty_params (#self_param, value: #param_ty)
+                        -> #return_ty
+                    {
+                        let mut new = #self_into_return_ty;
+                        new.#field_ident = #into_value;
+                        #discard_errors
+                        #return_new
+                    }
+                ));
+            }
 
             if self.try_setter {
                 let try_ty_params =
@@ -159,7 +308,27 @@ impl<'a> ToTokens for Setter<'a> {
                     converted = wrap_expression_in_some(crate_root, converted);
                 }
 
-                tokens.append_all(quote!(
+                if self.defer_try_setter_errors {
+                    tokens.append_all(quote!(
+                        #(#attrs)*
+                        #[allow(unused_mut)]
+                        #vis fn #try_ident #try_ty_params (#self_param, value: VALUE) -> #return_ty
+                        where
+                            VALUE::Error: #crate_root::export::core::fmt::Display,
+                        {
+                            let mut new = #self_into_return_ty;
+                            match value.try_into() {
+                                Ok(converted) => {
+                                    new.#field_ident = #converted;
+                                    #discard_errors
+                                }
+                                Err(error) => #record_error,
+                            }
+                            #return_new
+                        }
+                    ));
+                } else {
+                    tokens.append_all(quote!(
                     #(#attrs)*
                     #vis fn #try_ident #try_ty_params (#self_param, value: VALUE)
                         -> #crate_root::export::core::result::Result<#return_ty, VALUE::Error>
@@ -167,9 +336,97 @@ impl<'a> ToTokens for Setter<'a> {
                         let converted : #ty = value.try_into()?;
                         let mut new = #self_into_return_ty;
                         new.#field_ident = #converted;
-                        Ok(new)
+                        Ok(#return_new)
+                    }
+                    ));
+                }
+            }
+
+            if let (Some(fallback), true) = (&self.strip_option_fallback, stripped_option) {
+                // A transform computes the value of the main setter only, so this setter takes
+                // the field type like the main setter would without `into`.
+                let mut fallback_value = if self.generic_into && self.transform.is_none() {
+                    quote!(#crate_root::export::core::option::Option::map(
+                        value,
+                        #crate_root::export::core::convert::Into::into
+                    ))
+                } else {
+                    quote!(value)
+                };
+                if builder_field_is_option {
+                    fallback_value = wrap_expression_in_some(crate_root, fallback_value);
+                }
+
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #[allow(unused_mut)]
+                    #vis fn #fallback #ty_params (
+                        #self_param,
+                        value: #crate_root::export::core::option::Option<#param_ty>
+                    ) -> #return_ty
+                    {
+                        let mut new = #self_into_return_ty;
+                        new.#field_ident = #fallback_value;
+                        #discard_errors
+                        #return_new
                     }
                 ));
+
+                if self.try_setter {
+                    let try_ty_params =
+                        quote!(<VALUE: #crate_root::export::core::convert::TryInto<#ty>>);
+                    let try_fallback = format_ident!("try_{}", fallback);
+                    let try_value = quote!(#crate_root::export::core::option::Option::transpose(
+                        #crate_root::export::core::option::Option::map(
+                            value,
+                            #crate_root::export::core::convert::TryInto::try_into
+                        )
+                    ));
+
+                    let mut converted = quote!(converted);
+                    if builder_field_is_option {
+                        converted = wrap_expression_in_some(crate_root, converted);
+                    }
+
+                    if self.defer_try_setter_errors {
+                        tokens.append_all(quote!(
+                            #(#attrs)*
+                            #[allow(unused_mut)]
+                            #vis fn #try_fallback #try_ty_params (
+                                #self_param,
+                                value: #crate_root::export::core::option::Option<VALUE>
+                            ) -> #return_ty
+                            where
+                                VALUE::Error: #crate_root::export::core::fmt::Display,
+                            {
+                                let mut new = #self_into_return_ty;
+                                match #try_value {
+                                    Ok(converted) => {
+                                        new.#field_ident = #converted;
+                                        #discard_errors
+                                    }
+                                    Err(error) => #record_error,
+                                }
+                                #return_new
+                            }
+                        ));
+                    } else {
+                        tokens.append_all(quote!(
+                            #(#attrs)*
+                            #vis fn #try_fallback #try_ty_params (
+                                #self_param,
+                                value: #crate_root::export::core::option::Option<VALUE>
+                            ) -> #crate_root::export::core::result::Result<#return_ty, VALUE::Error>
+                            {
+                                let converted: #crate_root::export::core::option::Option<#ty> =
+                                    #try_value?;
+                                let mut new = #self_into_return_ty;
+                                new.#field_ident = #converted;
+                                Ok(#return_new)
+                            }
+                        ));
+                    }
+                }
             }
 
             if let Some(each) = self.each {
@@ -190,38 +447,189 @@ impl<'a> ToTokens for Setter<'a> {
                 };
 
                 let ty_params: TokenStream;
-                let param_ty: TokenStream;
+                let params: TokenStream;
+                let item_ty: TokenStream;
                 let into_item: TokenStream;
+                let mut item_bound = TokenStream::new();
+                // Type parameters and conversion of the items of the `extend_` method.
+                let extend_ty_params: TokenStream;
+                let extend_item_ty: TokenStream;
+                let into_items: TokenStream;
 
-                if each.into {
+                if each.key_value {
+                    ty_params = quote!(<
+                        KEY,
+                        VALUE,
+                        FROM_KEY: #crate_root::export::core::convert::Into<KEY>,
+                        FROM_VALUE: #crate_root::export::core::convert::Into<VALUE>
+                    >);
+                    params = quote!(key: FROM_KEY, value: FROM_VALUE);
+                    item_ty = quote!((KEY, VALUE));
+                    // Maps also extend from references to `Copy` keys and values, so the key
+                    // and value types are taken from the items of the collection instead.
+                    item_bound = quote!(
+                        + #crate_root::export::core::iter::IntoIterator<Item = (KEY, VALUE)>
+                    );
+                    into_item = quote!((
+                        #crate_root::export::core::convert::Into::into(key),
+                        #crate_root::export::core::convert::Into::into(value)
+                    ));
+                    extend_ty_params = quote!(<
+                        KEY,
+                        VALUE,
+                        FROM_KEY: #crate_root::export::core::convert::Into<KEY>,
+                        FROM_VALUE: #crate_root::export::core::convert::Into<VALUE>,
+                        ITEMS: #crate_root::export::core::iter::IntoIterator<Item = (FROM_KEY, FROM_VALUE)>
+                    >);
+                    extend_item_ty = item_ty.clone();
+                    into_items = quote!(#crate_root::export::core::iter::Iterator::map(
+                        #crate_root::export::core::iter::IntoIterator::into_iter(items),
+                        |(key, value)| #into_item
+                    ));
+                } else if each.into {
                     ty_params = quote!(<VALUE, FROM_VALUE: #crate_root::export::core::convert::Into<VALUE>>);
-                    param_ty = quote!(FROM_VALUE);
+                    params = quote!(item: FROM_VALUE);
+                    item_ty = quote!(VALUE);
                     into_item = quote!(#crate_root::export::core::convert::Into::into(item));
+                    extend_ty_params = quote!(<
+                        VALUE,
+                        FROM_VALUE: #crate_root::export::core::convert::Into<VALUE>,
+                        ITEMS: #crate_root::export::core::iter::IntoIterator<Item = FROM_VALUE>
+                    >);
+                    extend_item_ty = quote!(VALUE);
+                    into_items = quote!(#crate_root::export::core::iter::Iterator::map(
+                        #crate_root::export::core::iter::IntoIterator::into_iter(items),
+                        #crate_root::export::core::convert::Into::into
+                    ));
                 } else {
                     ty_params = quote!(<VALUE>);
-                    param_ty = quote!(VALUE);
+                    params = quote!(item: VALUE);
+                    item_ty = quote!(VALUE);
                     into_item = quote!(item);
+                    extend_ty_params =
+                        quote!(<ITEMS: #crate_root::export::core::iter::IntoIterator>);
+                    extend_item_ty = quote!(ITEMS::Item);
+                    into_items = quote!(items);
                 }
 
                 tokens.append_all(quote!(
                     #(#attrs)*
                     #[allow(unused_mut)]
-                    #vis fn #ident_each #ty_params(#self_param, item: #param_ty) -> #return_ty
+                    #vis fn #ident_each #ty_params(#self_param, #params) -> #return_ty
                     where
-                        #ty: #crate_root::export::core::default::Default + #crate_root::export::core::iter::Extend<VALUE>,
+                        #ty: #crate_root::export::core::default::Default + #crate_root::export::core::iter::Extend<#item_ty> #item_bound,
                     {
                         let mut new = #self_into_return_ty;
                         new.#field_ident
                             .#get_initialized_collection
                             .extend(#crate_root::export::core::option::Option::Some(#into_item));
-                        new
+                        #return_new
+                    }
+                ));
+
+                // Named after the setter, dropping leading underscores, e.g. for the fields of
+                // tuple structs.
+                let ident_extend = format_ident!(
+                    "extend_{}",
+                    ident.unraw().to_string().trim_start_matches('_')
+                );
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #[allow(unused_mut)]
+                    #vis fn #ident_extend #extend_ty_params(#self_param, items: ITEMS) -> #return_ty
+                    where
+                        #ty: #crate_root::export::core::default::Default + #crate_root::export::core::iter::Extend<#extend_item_ty> #item_bound,
+                    {
+                        let mut new = #self_into_return_ty;
+                        new.#field_ident
+                            .#get_initialized_collection
+                            .extend(#into_items);
+                        #return_new
                     }
                 ));
+
+                // The conversions of a key and a value would fail with different errors.
+                if self.try_setter && !each.key_value && self.defer_try_setter_errors {
+                    let try_ident_each = format_ident!("try_{}", ident_each);
+                    tokens.append_all(quote!(
+                        #(#attrs)*
+                        #[allow(unused_mut)]
+                        #vis fn #try_ident_each<VALUE, FROM_VALUE: #crate_root::export::core::convert::TryInto<VALUE>>(
+                            #self_param,
+                            item: FROM_VALUE
+                        ) -> #return_ty
+                        where
+                            #ty: #crate_root::export::core::default::Default
+                                + #crate_root::export::core::iter::Extend<VALUE>
+                                + #crate_root::export::core::iter::IntoIterator<Item = VALUE>,
+                            FROM_VALUE::Error: #crate_root::export::core::fmt::Display,
+                        {
+                            let mut new = #self_into_return_ty;
+                            match item.try_into() {
+                                Ok(converted) => new.#field_ident
+                                    .#get_initialized_collection
+                                    .extend(#crate_root::export::core::option::Option::Some(converted)),
+                                Err(error) => #record_error,
+                            }
+                            #return_new
+                        }
+                    ));
+                } else if self.try_setter && !each.key_value {
+                    let try_ident_each = format_ident!("try_{}", ident_each);
+                    tokens.append_all(quote!(
+                        #(#attrs)*
+                        #vis fn #try_ident_each<VALUE, FROM_VALUE: #crate_root::export::core::convert::TryInto<VALUE>>(
+                            #self_param,
+                            item: FROM_VALUE
+                        ) -> #crate_root::export::core::result::Result<#return_ty, FROM_VALUE::Error>
+                        where
+                            #ty: #crate_root::export::core::default::Default
+                                + #crate_root::export::core::iter::Extend<VALUE>
+                                + #crate_root::export::core::iter::IntoIterator<Item = VALUE>,
+                        {
+                            let converted: VALUE = item.try_into()?;
+                            let mut new = #self_into_return_ty;
+                            new.#field_ident
+                                .#get_initialized_collection
+                                .extend(#crate_root::export::core::option::Option::Some(converted));
+                            Ok(#return_new)
+                        }
+                    ));
+                }
             }
         }
     }
 }
 
+/// Field of the builder in which fallible setters record their errors, if they are deferred
+/// to the build method with `#[builder(try_setter(defer))]`.
+#[derive(Debug)]
+pub struct TrySetterErrorsField<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+}
+
+impl<'a> TrySetterErrorsField<'a> {
+    /// Name of the field.
+    pub fn ident() -> syn::Ident {
+        syn::Ident::new(TRY_SETTER_ERRORS_NAME, Span::call_site())
+    }
+
+    /// Field declaration for the builder struct.
+    pub fn declaration(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = Self::ident();
+        quote!(#ident: #crate_root::export::core::vec::Vec<#crate_root::TrySetterError>,)
+    }
+
+    /// Field initializer for the builder struct.
+    pub fn initializer(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = Self::ident();
+        quote!(#ident: #crate_root::export::core::default::Default::default(),)
+    }
+}
+
 /// Returns expression wrapping `bare_value` in `Some`
 fn wrap_expression_in_some(crate_root: &syn::Path, bare_value: impl ToTokens) -> TokenStream {
     quote!( #crate_root::export::core::option::Option::Some(#bare_value) )
@@ -231,8 +639,8 @@ fn wrap_expression_in_some(crate_root: &syn::Path, bare_value: impl ToTokens) ->
 // Note that since syn is a parser, it works with tokens.
 // We cannot know for sure that this is an Option.
 // The user could, for example, `type MaybeString = std::option::Option<String>`
-// We cannot handle those arbitrary names.
-fn extract_type_from_option(ty: &syn::Type) -> Option<&syn::Type> {
+// We cannot handle those arbitrary names, which need `strip_option(inner = "...")`.
+pub(crate) fn extract_type_from_option(ty: &syn::Type) -> Option<&syn::Type> {
     use syn::punctuated::Pair;
     use syn::token::PathSep;
     use syn::{GenericArgument, Path, PathArguments, PathSegment};
@@ -286,6 +694,7 @@ macro_rules! default_setter {
             crate_root: &parse_quote!(::db),
             setter_enabled: true,
             try_setter: false,
+            defer_try_setter_errors: false,
             visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
             pattern: BuilderPattern::Mutable,
             attrs: &[],
@@ -294,7 +703,13 @@ macro_rules! default_setter {
             field_type: BuilderFieldType::Optional(Box::leak(Box::new(parse_quote!(Foo)))),
             generic_into: false,
             strip_option: false,
+            strip_option_inner: None,
+            strip_option_fallback: None,
             each: None,
+            transform: None,
+            typestate: None,
+            sub_builder: false,
+            flattened: vec![],
         }
     };
 }
@@ -495,6 +910,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn strip_option_inner() {
+        let ty = parse_quote!(MaybeFoo);
+        let inner = parse_quote!(Foo);
+        let mut setter = default_setter!();
+        setter.strip_option = true;
+        setter.strip_option_inner = Some(&inner);
+        setter.field_type = BuilderFieldType::Optional(&ty);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(value)
+                    );
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn strip_option_fallback() {
+        let ty = parse_quote!(Option<Foo>);
+        let mut setter = default_setter!();
+        setter.strip_option = true;
+        setter.strip_option_fallback = Some(parse_quote!(maybe_foo));
+        setter.try_setter = true;
+        setter.generic_into = true;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(value.into())
+                    );
+                    new
+                }
+                pub fn try_foo<VALUE: ::db::export::core::convert::TryInto<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> ::db::export::core::result::Result<&mut Self, VALUE::Error> {
+                    let converted: Foo = value.try_into()?;
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(converted)
+                    );
+                    Ok(new)
+                }
+                #[allow(unused_mut)]
+                pub fn maybe_foo<VALUE: ::db::export::core::convert::Into<Foo>>(
+                    &mut self,
+                    value: ::db::export::core::option::Option<VALUE>
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::map(
+                            value,
+                            ::db::export::core::convert::Into::into
+                        )
+                    );
+                    new
+                }
+                pub fn try_maybe_foo<VALUE: ::db::export::core::convert::TryInto<Foo>>(
+                    &mut self,
+                    value: ::db::export::core::option::Option<VALUE>
+                ) -> ::db::export::core::result::Result<&mut Self, VALUE::Error> {
+                    let converted: ::db::export::core::option::Option<Foo> =
+                        ::db::export::core::option::Option::transpose(
+                            ::db::export::core::option::Option::map(
+                                value,
+                                ::db::export::core::convert::TryInto::try_into
+                            )
+                        )?;
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(converted);
+                    Ok(new)
+                }
+            )
+            .to_string()
+        );
+    }
+
     // including try_setter
     #[test]
     fn full() {
@@ -612,12 +1123,336 @@ mod tests {
         );
     }
 
+    #[test]
+    fn typestate() {
+        let mut setter: Setter = default_setter!();
+        setter.pattern = BuilderPattern::Owned;
+        setter.typestate = Some(quote!(FooBuilder<::db::typestate::Set>));
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(self, value: Foo) -> FooBuilder<::db::typestate::Set> {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new.__into_typestate()
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn sub_builder() {
+        let ty = parse_quote!(FooBuilder);
+        let mut setter: Setter = default_setter!();
+        setter.field_type = BuilderFieldType::Precise(&ty);
+        setter.sub_builder = true;
+        // Neither of these apply to sub-builder fields
+        setter.try_setter = true;
+        setter.generic_into = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                pub fn foo_mut(&mut self) -> &mut FooBuilder {
+                    &mut self.foo
+                }
+
+                #[allow(unused_mut)]
+                pub fn foo_with<BUILD: ::db::export::core::ops::FnOnce(&mut FooBuilder)>(&mut self, build: BUILD)
+                    -> &mut Self
+                {
+                    let mut new = self;
+                    build(&mut new.foo);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn flattened() {
+        let ty = parse_quote!(FooBuilder);
+        let (bar, bar_ty) = (parse_quote!(bar), parse_quote!(u32));
+        let mut setter: Setter = default_setter!();
+        setter.field_type = BuilderFieldType::Precise(&ty);
+        setter.sub_builder = true;
+        setter.generic_into = true;
+        setter.pattern = BuilderPattern::Owned;
+        setter.flattened = vec![FlattenedSetter {
+            ident: parse_quote!(foo_bar),
+            setter: &bar,
+            ty: &bar_ty,
+        }];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                pub fn foo_mut(&mut self) -> &mut FooBuilder {
+                    &mut self.foo
+                }
+
+                #[allow(unused_mut)]
+                pub fn foo_with<BUILD: ::db::export::core::ops::FnOnce(&mut FooBuilder)>(self, build: BUILD)
+                    -> Self
+                {
+                    let mut new = self;
+                    build(&mut new.foo);
+                    new
+                }
+
+                #[doc = "Calls `bar` on the builder of `foo`."]
+                #[allow(unused_mut)]
+                pub fn foo_bar<VALUE: ::db::export::core::convert::Into<u32>>(self, value: VALUE)
+                    -> Self
+                {
+                    let mut new = self;
+                    let _: &mut FooBuilder = new.foo.bar(value.into());
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn each_key_value() {
+        let ty = parse_quote!(HashMap<String, u32>);
+        let each = Each {
+            key_value: true,
+            ..Each::from(syn::Ident::new("foo_entry", Span::call_site()))
+        };
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.each = Some(&each);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: HashMap<String, u32>) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+
+                #[allow(unused_mut)]
+                pub fn foo_entry<
+                    KEY,
+                    VALUE,
+                    FROM_KEY: ::db::export::core::convert::Into<KEY>,
+                    FROM_VALUE: ::db::export::core::convert::Into<VALUE>
+                >(&mut self, key: FROM_KEY, value: FROM_VALUE) -> &mut Self
+                where
+                    HashMap<String, u32>: ::db::export::core::default::Default
+                        + ::db::export::core::iter::Extend<(KEY, VALUE)>
+                        + ::db::export::core::iter::IntoIterator<Item = (KEY, VALUE)>,
+                {
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(::db::export::core::default::Default::default)
+                        .extend(::db::export::core::option::Option::Some((
+                            ::db::export::core::convert::Into::into(key),
+                            ::db::export::core::convert::Into::into(value)
+                        )));
+                    new
+                }
+
+                #[allow(unused_mut)]
+                pub fn extend_foo<
+                    KEY,
+                    VALUE,
+                    FROM_KEY: ::db::export::core::convert::Into<KEY>,
+                    FROM_VALUE: ::db::export::core::convert::Into<VALUE>,
+                    ITEMS: ::db::export::core::iter::IntoIterator<Item = (FROM_KEY, FROM_VALUE)>
+                >(&mut self, items: ITEMS) -> &mut Self
+                where
+                    HashMap<String, u32>: ::db::export::core::default::Default
+                        + ::db::export::core::iter::Extend<(KEY, VALUE)>
+                        + ::db::export::core::iter::IntoIterator<Item = (KEY, VALUE)>,
+                {
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(::db::export::core::default::Default::default)
+                        .extend(::db::export::core::iter::Iterator::map(
+                            ::db::export::core::iter::IntoIterator::into_iter(items),
+                            |(key, value)| (
+                                ::db::export::core::convert::Into::into(key),
+                                ::db::export::core::convert::Into::into(value)
+                            )
+                        ));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn transform() {
+        let ty = parse_quote!(Option<SocketAddr>);
+        let transform = SetterTransform {
+            closure: parse_quote!(|host: &str, port: u16| SocketAddr::new(
+                host.parse().unwrap(),
+                port
+            )),
+        };
+        let mut setter: Setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = true;
+        setter.generic_into = true;
+        setter.transform = Some(&transform);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, host: &str, port: u16) -> &mut Self {
+                    let value: SocketAddr = (|host: &str, port: u16| SocketAddr::new(host.parse().unwrap(), port))(host, port);
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(value)
+                    );
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn try_setter_defer() {
+        let mut setter: Setter = default_setter!();
+        setter.pattern = BuilderPattern::Owned;
+        setter.try_setter = true;
+        setter.defer_try_setter_errors = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(self, value: Foo) -> Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new.__try_setter_errors.retain(|error| error.field_name() != "foo");
+                    new
+                }
+
+                #[allow(unused_mut)]
+                pub fn try_foo<VALUE: ::db::export::core::convert::TryInto<Foo>>(self, value: VALUE) -> Self
+                where
+                    VALUE::Error: ::db::export::core::fmt::Display,
+                {
+                    let mut new = self;
+                    match value.try_into() {
+                        Ok(converted) => {
+                            new.foo = ::db::export::core::option::Option::Some(converted);
+                            new.__try_setter_errors.retain(|error| error.field_name() != "foo");
+                        }
+                        Err(error) => new.__try_setter_errors.push(::db::TrySetterError::new(
+                            "foo",
+                            ::db::export::core::string::ToString::to_string(&error)
+                        )),
+                    }
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn each_try_setter() {
+        let ty = parse_quote!(Vec<Foo>);
+        let each = Each::from(syn::Ident::new("foo_item", Span::call_site()));
+        let mut setter = default_setter!();
+        setter.pattern = BuilderPattern::Owned;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.each = Some(&each);
+        setter.try_setter = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(self, value: Vec<Foo>) -> Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+
+                pub fn try_foo<VALUE: ::db::export::core::convert::TryInto<Vec<Foo> >>(self, value: VALUE)
+                    -> ::db::export::core::result::Result<Self, VALUE::Error>
+                {
+                    let converted: Vec<Foo> = value.try_into()?;
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(converted);
+                    Ok(new)
+                }
+
+                #[allow(unused_mut)]
+                pub fn foo_item<VALUE>(self, item: VALUE) -> Self
+                where
+                    Vec<Foo>: ::db::export::core::default::Default
+                        + ::db::export::core::iter::Extend<VALUE>,
+                {
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(::db::export::core::default::Default::default)
+                        .extend(::db::export::core::option::Option::Some(item));
+                    new
+                }
+
+                #[allow(unused_mut)]
+                pub fn extend_foo<ITEMS: ::db::export::core::iter::IntoIterator>(self, items: ITEMS) -> Self
+                where
+                    Vec<Foo>: ::db::export::core::default::Default
+                        + ::db::export::core::iter::Extend<ITEMS::Item>,
+                {
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(::db::export::core::default::Default::default)
+                        .extend(items);
+                    new
+                }
+
+                pub fn try_foo_item<VALUE, FROM_VALUE: ::db::export::core::convert::TryInto<VALUE>>(
+                    self,
+                    item: FROM_VALUE
+                ) -> ::db::export::core::result::Result<Self, FROM_VALUE::Error>
+                where
+                    Vec<Foo>: ::db::export::core::default::Default
+                        + ::db::export::core::iter::Extend<VALUE>
+                        + ::db::export::core::iter::IntoIterator<Item = VALUE>,
+                {
+                    let converted: VALUE = item.try_into()?;
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(::db::export::core::default::Default::default)
+                        .extend(::db::export::core::option::Option::Some(converted));
+                    Ok(new)
+                }
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn extract_type_from_option_on_simple_type() {
         let ty_foo = parse_quote!(Foo);
         assert_eq!(extract_type_from_option(&ty_foo), None);
 
-        for s in vec![
+        for s in [
             parse_quote!(Option<Foo>),
             parse_quote!(std::option::Option<Foo>),
             parse_quote!(::std::option::Option<Foo>),
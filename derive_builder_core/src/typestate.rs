@@ -0,0 +1,315 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+use syn::{GenericParam, Generics};
+
+/// Name of the `PhantomData` field which holds the typestate parameters.
+pub(crate) const TYPESTATE_FIELD_NAME: &str = "__typestate";
+
+/// Name of the builder method which moves all fields into a builder with different typestate
+/// parameters.
+pub(crate) const TYPESTATE_TRANSITION_NAME: &str = "__into_typestate";
+
+/// Type-level tracking of required fields for builders generated with `#[builder(typestate)]`.
+///
+/// Every required field gets a type parameter on the builder struct, which is
+/// `derive_builder::typestate::Unset` until its setter is called and
+/// `derive_builder::typestate::Set` afterwards. The build method is only implemented
+/// for the builder type where all parameters are `Set`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// pub struct FooBuilder<__Foo = ::derive_builder::typestate::Unset> {
+///     foo: Option<u32>,
+///     __typestate: PhantomData<(__Foo,)>,
+/// }
+///
+/// impl<__Foo> FooBuilder<__Foo> {
+///     pub fn foo(self, value: u32) -> FooBuilder<::derive_builder::typestate::Set> {
+///         let mut new = self;
+///         new.foo = Some(value);
+///         new.__into_typestate()
+///     }
+///
+///     fn __into_typestate<__FooNext>(self) -> FooBuilder<__FooNext> {
+///         FooBuilder { foo: self.foo, __typestate: PhantomData }
+///     }
+/// }
+///
+/// impl FooBuilder<::derive_builder::typestate::Set> {
+///     pub fn build(self) -> Foo {
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Typestate<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the builder struct.
+    pub builder_ident: syn::Ident,
+    /// Type parameters and lifetimes of the target struct.
+    pub generics: Option<&'a Generics>,
+    /// Type parameters tracking whether each required field has been set.
+    pub params: Vec<syn::Ident>,
+    /// All fields of the builder struct, together with the `cfg` attributes that apply to them.
+    pub fields: Vec<(syn::Ident, Vec<&'a syn::Attribute>)>,
+    /// The build method, which is only implemented once every required field has been set.
+    pub build_fn: TokenStream,
+}
+
+impl<'a> Typestate<'a> {
+    /// Generics of the builder struct declaration: the generics of the target struct, followed
+    /// by one parameter per required field which defaults to `Unset`.
+    pub fn struct_generics(&self) -> Generics {
+        let crate_root = self.crate_root;
+        let mut generics = self.generics.cloned().unwrap_or_default();
+        for param in &self.params {
+            generics
+                .params
+                .push(parse_quote!(#param = #crate_root::typestate::Unset));
+        }
+        generics
+    }
+
+    /// Add the typestate parameters to the generics of an `impl` block.
+    pub fn extend_impl_generics(&self, generics: &mut Generics) {
+        for param in &self.params {
+            generics.params.push(parse_quote!(#param));
+        }
+    }
+
+    /// The builder type in which every required field is `Unset`.
+    pub fn unset_ty(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        self.ty_with(
+            self.params
+                .iter()
+                .map(|_| quote!(#crate_root::typestate::Unset)),
+        )
+    }
+
+    /// The builder type in which every required field is `Set`.
+    pub fn set_ty(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        self.ty_with(
+            self.params
+                .iter()
+                .map(|_| quote!(#crate_root::typestate::Set)),
+        )
+    }
+
+    /// The builder type returned by the setter of the field tracked by `param`: that field
+    /// becomes `Set`, all others keep their current state.
+    pub fn transition_ty(&self, param: &syn::Ident) -> TokenStream {
+        let crate_root = self.crate_root;
+        self.ty_with(self.params.iter().map(|p| {
+            if p == param {
+                quote!(#crate_root::typestate::Set)
+            } else {
+                p.to_token_stream()
+            }
+        }))
+    }
+
+    /// Field declaration which uses all typestate parameters.
+    pub fn field(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = syn::Ident::new(TYPESTATE_FIELD_NAME, Span::call_site());
+        let params = &self.params;
+        quote!(#ident: #crate_root::export::core::marker::PhantomData<(#(#params,)*)>,)
+    }
+
+    /// Field initializer for the field emitted by `Typestate::field`.
+    pub fn field_initializer(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = syn::Ident::new(TYPESTATE_FIELD_NAME, Span::call_site());
+        quote!(#ident: #crate_root::export::core::marker::PhantomData,)
+    }
+
+    /// The builder type with the generics of the target struct followed by `states`.
+    fn ty_with(&self, states: impl Iterator<Item = TokenStream>) -> TokenStream {
+        let builder_ident = &self.builder_ident;
+        let args: Vec<_> = self
+            .generics
+            .into_iter()
+            .flat_map(|g| g.params.iter())
+            .map(|param| match param {
+                GenericParam::Lifetime(l) => l.lifetime.to_token_stream(),
+                GenericParam::Type(t) => t.ident.to_token_stream(),
+                GenericParam::Const(c) => c.ident.to_token_stream(),
+            })
+            .chain(states)
+            .collect();
+
+        if args.is_empty() {
+            builder_ident.to_token_stream()
+        } else {
+            quote!(#builder_ident<#(#args),*>)
+        }
+    }
+}
+
+impl<'a> ToTokens for Typestate<'a> {
+    /// Emits the method which changes the typestate parameters of the builder.
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if self.params.is_empty() {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let builder_ident = &self.builder_ident;
+        let transition = syn::Ident::new(TYPESTATE_TRANSITION_NAME, Span::call_site());
+        let typestate_field = syn::Ident::new(TYPESTATE_FIELD_NAME, Span::call_site());
+        let next_params: Vec<_> = self
+            .params
+            .iter()
+            .map(|p| format_ident!("{}Next", p))
+            .collect();
+        let next_ty = self.ty_with(next_params.iter().map(ToTokens::to_token_stream));
+        let moved_fields = self
+            .fields
+            .iter()
+            .map(|(ident, attrs)| quote!(#(#attrs)* #ident: self.#ident,));
+
+        tokens.append_all(quote!(
+            fn #transition<#(#next_params),*>(self) -> #next_ty {
+                #builder_ident {
+                    #(#moved_fields)*
+                    #typestate_field: #crate_root::export::core::marker::PhantomData,
+                }
+            }
+        ));
+    }
+}
+
+/// Get the ident of the type parameter which tracks whether `field` has been set.
+///
+/// The field name is converted to upper camel case, so `server_name` is tracked by `__ServerName`.
+pub fn typestate_param(field: &syn::Ident) -> syn::Ident {
+    let camel_case: String = field
+        .unraw()
+        .to_string()
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect();
+    format_ident!("__{}", camel_case)
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_typestate {
+    () => {
+        Typestate {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            builder_ident: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            params: vec![syn::Ident::new("__Foo", ::proc_macro2::Span::call_site())],
+            fields: vec![],
+            build_fn: quote!(
+                fn build(self) -> Foo {
+                    unimplemented!()
+                }
+            ),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn param_names() {
+        assert_eq!(
+            typestate_param(&parse_quote!(foo)).to_string(),
+            "__Foo".to_string()
+        );
+        assert_eq!(
+            typestate_param(&parse_quote!(server_name)).to_string(),
+            "__ServerName".to_string()
+        );
+        assert_eq!(
+            typestate_param(&parse_quote!(r#type)).to_string(),
+            "__Type".to_string()
+        );
+    }
+
+    #[test]
+    fn transition() {
+        let foo: syn::Ident = parse_quote!(foo);
+        let bar: syn::Ident = parse_quote!(bar);
+        let mut typestate = default_typestate!();
+        typestate.params.push(parse_quote!(__Bar));
+        typestate.fields = vec![(foo, vec![]), (bar, vec![])];
+
+        assert_eq!(
+            typestate.transition_ty(&parse_quote!(__Bar)).to_string(),
+            quote!(FooBuilder<__Foo, ::db::typestate::Set>).to_string()
+        );
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#typestate).to_string(),
+            quote!(
+                fn __into_typestate<__FooNext, __BarNext>(self) -> FooBuilder<__FooNext, __BarNext> {
+                    FooBuilder {
+                        foo: self.foo,
+                        bar: self.bar,
+                        __typestate: ::db::export::core::marker::PhantomData,
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generics() {
+        let ast: syn::DeriveInput = parse_quote! {
+            struct Lorem<'a, T: Debug, const N: usize> where T: PartialEq { }
+        };
+        let mut typestate = default_typestate!();
+        typestate.generics = Some(&ast.generics);
+
+        assert_eq!(
+            typestate.struct_generics().to_token_stream().to_string(),
+            quote!(<'a, T: Debug, const N: usize, __Foo = ::db::typestate::Unset>).to_string()
+        );
+        assert_eq!(
+            typestate.set_ty().to_string(),
+            quote!(FooBuilder<'a, T, N, ::db::typestate::Set>).to_string()
+        );
+        assert_eq!(
+            typestate.unset_ty().to_string(),
+            quote!(FooBuilder<'a, T, N, ::db::typestate::Unset>).to_string()
+        );
+    }
+
+    #[test]
+    fn no_required_fields() {
+        let mut typestate = default_typestate!();
+        typestate.params.clear();
+
+        assert_eq!(quote!(#typestate).to_string(), quote!().to_string());
+        assert_eq!(
+            typestate.set_ty().to_string(),
+            quote!(FooBuilder).to_string()
+        );
+    }
+}
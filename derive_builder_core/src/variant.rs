@@ -0,0 +1,234 @@
+use std::borrow::Cow;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+
+/// Name of the `PhantomData` field which makes the builder of an enum variant use all
+/// generic parameters of the enum.
+pub(crate) const VARIANT_PHANTOM_FIELD_NAME: &str = "__enum";
+
+/// Associated function of the builder of an enum, which starts building one of its
+/// variants, implementing `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::VariantBuilderFn;
+/// # fn main() {
+/// #    let variant_fn = default_variant_builder_fn!();
+/// #
+/// #    assert_eq!(quote!(#variant_fn).to_string(), quote!(
+/// pub fn circle() -> ShapeCircleBuilder {
+///     ShapeCircleBuilder::create_empty()
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct VariantBuilderFn<'a> {
+    /// Name of this function, e.g. `circle` for the variant `Circle`.
+    pub ident: syn::Ident,
+    /// Visibility of this function, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Name of the builder struct of the variant.
+    pub builder_ident: syn::Ident,
+    /// Type parameters and lifetimes of the variant builder's `impl` blocks, including bounds.
+    pub generics: Option<&'a syn::Generics>,
+    /// The identifier of the inherent method which creates an empty variant builder.
+    pub create_empty: &'a syn::Ident,
+    /// Doc-comment of this function.
+    pub doc_comment: Option<syn::Attribute>,
+}
+
+impl<'a> ToTokens for VariantBuilderFn<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+        let vis = &self.visibility;
+        let builder_ident = &self.builder_ident;
+        let create_empty = self.create_empty;
+        let doc_comment = &self.doc_comment;
+        let (impl_generics, ty_generics, where_clause) = self
+            .generics
+            .map(syn::Generics::split_for_impl)
+            .map(|(i, t, w)| (Some(i), Some(t), w))
+            .unwrap_or((None, None, None));
+
+        tokens.append_all(quote!(
+            #doc_comment
+            #vis fn #ident #impl_generics () -> #builder_ident #ty_generics #where_clause {
+                #builder_ident::#create_empty()
+            }
+        ));
+    }
+}
+
+/// Field of a variant builder which uses all generic parameters of the enum, as the
+/// fields of the variant might not.
+///
+/// This is only needed if the enum has generic parameters.
+#[derive(Debug)]
+pub struct VariantPhantomField<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the enum.
+    pub target_ty: &'a syn::Ident,
+    /// Type parameters and lifetimes of the enum.
+    pub generics: &'a syn::Generics,
+}
+
+impl<'a> VariantPhantomField<'a> {
+    /// Name of the field.
+    pub fn ident() -> syn::Ident {
+        syn::Ident::new(VARIANT_PHANTOM_FIELD_NAME, Span::call_site())
+    }
+
+    /// Field declaration for the builder struct.
+    pub fn declaration(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = Self::ident();
+        let target_ty = self.target_ty;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote!(#ident: #crate_root::export::core::marker::PhantomData<#target_ty #ty_generics>,)
+    }
+
+    /// Field initializer for the builder struct.
+    pub fn initializer(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let ident = Self::ident();
+        quote!(#ident: #crate_root::export::core::marker::PhantomData,)
+    }
+}
+
+/// Get the ident of the function which starts building `variant`.
+///
+/// The variant name is converted to snake case, so `HttpServer` becomes `http_server`.
+pub fn variant_fn_ident(variant: &syn::Ident) -> syn::Ident {
+    let chars: Vec<char> = variant.unraw().to_string().chars().collect();
+    let mut snake_case = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = if i > 0 { chars.get(i - 1) } else { None };
+            let next = chars.get(i + 1);
+            let word_start = match prev {
+                Some(p) if p.is_lowercase() || p.is_numeric() => true,
+                Some(p) if p.is_uppercase() => next.map_or(false, |n| n.is_lowercase()),
+                _ => false,
+            };
+            if word_start {
+                snake_case.push('_');
+            }
+            snake_case.extend(c.to_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+
+    match snake_case.as_str() {
+        // These keywords cannot be raw identifiers
+        "self" | "super" | "crate" => syn::Ident::new(&format!("{}_", snake_case), variant.span()),
+        // Other keywords, e.g. `type`, are not valid identifiers unless they are raw
+        _ if syn::parse_str::<syn::Ident>(&snake_case).is_err() => {
+            syn::Ident::new_raw(&snake_case, variant.span())
+        }
+        _ => syn::Ident::new(&snake_case, variant.span()),
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_variant_builder_fn {
+    () => {
+        VariantBuilderFn {
+            ident: syn::Ident::new("circle", ::proc_macro2::Span::call_site()),
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            builder_ident: syn::Ident::new("ShapeCircleBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            create_empty: &syn::Ident::new("create_empty", ::proc_macro2::Span::call_site()),
+            doc_comment: None,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let variant_fn = default_variant_builder_fn!();
+
+        assert_eq!(
+            quote!(#variant_fn).to_string(),
+            quote!(
+                pub fn circle() -> ShapeCircleBuilder {
+                    ShapeCircleBuilder::create_empty()
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generic() {
+        let generics: syn::Generics = parse_quote!(<'a, T: Clone>);
+        let mut variant_fn = default_variant_builder_fn!();
+        variant_fn.generics = Some(&generics);
+
+        assert_eq!(
+            quote!(#variant_fn).to_string(),
+            quote!(
+                pub fn circle<'a, T: Clone>() -> ShapeCircleBuilder<'a, T> {
+                    ShapeCircleBuilder::create_empty()
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn phantom_field() {
+        let generics: syn::Generics = parse_quote!(<'a, T: Clone, const N: usize>);
+        let phantom = VariantPhantomField {
+            crate_root: &parse_quote!(::db),
+            target_ty: &parse_quote!(Shape),
+            generics: &generics,
+        };
+
+        assert_eq!(
+            phantom.declaration().to_string(),
+            quote!(__enum: ::db::export::core::marker::PhantomData<Shape<'a, T, N> >,).to_string()
+        );
+        assert_eq!(
+            phantom.initializer().to_string(),
+            quote!(__enum: ::db::export::core::marker::PhantomData,).to_string()
+        );
+    }
+
+    #[test]
+    fn fn_names() {
+        for (variant, expected) in [
+            ("Circle", "circle"),
+            ("HttpServer", "http_server"),
+            ("HTTPServer", "http_server"),
+            ("Ipv4Addr", "ipv4_addr"),
+            ("V2", "v2"),
+            ("Type", "r#type"),
+            ("Super", "super_"),
+        ] {
+            let ident = syn::Ident::new(variant, Span::call_site());
+            assert_eq!(variant_fn_ident(&ident).to_string(), expected);
+        }
+    }
+}
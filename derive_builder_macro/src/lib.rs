@@ -1,4 +1,4 @@
-//! Derive a builder for a struct
+//! Derive a builder for a struct, or generate one for a function
 
 #![crate_type = "proc-macro"]
 #![deny(warnings)]
@@ -27,3 +27,13 @@ pub fn derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as syn::DeriveInput);
     derive_builder_core::builder_for_struct(ast).into()
 }
+
+/// Create a builder struct for the parameters of a function, or of the associated
+/// functions of an `impl` block which are marked with `#[builder]`.
+///
+/// See the `derive_builder` crate documentation for more details.
+#[proc_macro_attribute]
+pub fn builder(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as syn::Item);
+    derive_builder_core::builder_for_fn(args.into(), item).into()
+}